@@ -6,7 +6,7 @@
 
 use linera_sdk::{
     abi::WithContractAbi,
-    linera_base_types::{AccountOwner, Amount, Timestamp},
+    linera_base_types::{AccountOwner, Amount, CryptoHash, Timestamp},
     views::{RootView, View},
     Contract, ContractRuntime,
 };
@@ -17,7 +17,7 @@ use speedbet_arena::{
 };
 use thiserror::Error;
 
-/// Declare the contract implementation
+// Declare the contract implementation
 linera_sdk::contract!(SpeedBetContract);
 
 /// The SpeedBet Arena contract
@@ -145,6 +145,84 @@ impl Contract for SpeedBetContract {
             Operation::UpdateSettings { fee_bps, min_bet, max_bet } => {
                 self.update_settings(fee_bps, min_bet, max_bet).await;
             }
+            Operation::SetFeeTiers { tiers } => {
+                self.set_fee_tiers(tiers).await;
+            }
+            Operation::SetRangeBuckets { edge_bps, payout_bps } => {
+                self.set_range_buckets(edge_bps, payout_bps).await;
+            }
+            Operation::SetDuelDurations { durations } => {
+                self.set_duel_durations(durations).await;
+            }
+            Operation::ListAsset {
+                symbol,
+                display_decimals,
+                quote_currency,
+                price_decimals,
+                min_bet,
+                max_bet,
+                oracle_sources,
+            } => {
+                let config = AssetConfig {
+                    asset: Asset::new(&symbol),
+                    display_decimals,
+                    quote_currency,
+                    price_decimals,
+                    enabled: true,
+                    min_bet: min_bet.unwrap_or(*self.state.min_bet.get()),
+                    max_bet: max_bet.unwrap_or(*self.state.max_bet.get()),
+                    oracle_sources,
+                    max_deviation_bps: None,
+                    max_staleness_micros: None,
+                    house_exposure_limit: Amount::ZERO,
+                };
+                self.list_asset(config).await;
+            }
+            Operation::SetAssetEnabled { asset, enabled } => {
+                self.set_asset_enabled(asset, enabled).await;
+            }
+            Operation::SetSettlementMode { duel_mode, mode } => {
+                self.set_settlement_mode(duel_mode, mode).await;
+            }
+            Operation::SetCircuitBreaker { asset, max_deviation_bps, max_staleness_micros } => {
+                self.set_circuit_breaker(asset, max_deviation_bps, max_staleness_micros).await;
+            }
+            Operation::ResetCircuitBreaker { asset } => {
+                self.reset_circuit_breaker(asset).await;
+            }
+            Operation::ClearDuel { duel_id } => {
+                self.clear_duel(duel_id).await;
+            }
+            Operation::VoidDuel { duel_id } => {
+                self.void_duel(duel_id).await;
+            }
+            Operation::SetQueueRateLimit { max_ops, window_micros } => {
+                self.set_queue_rate_limit(max_ops, window_micros).await;
+            }
+            Operation::SetCollusionLimits { window_micros, flag_threshold, max_pair_matches, opposite_ratio_bps } => {
+                self.set_collusion_limits(window_micros, flag_threshold, max_pair_matches, opposite_ratio_bps).await;
+            }
+            Operation::ClearPairFlag { player_a, player_b } => {
+                self.clear_pair_flag(player_a, player_b).await;
+            }
+            Operation::SetPriceHistoryLength { length } => {
+                self.set_price_history_length(length).await;
+            }
+            Operation::SetRakeback { rakeback_bps, epoch_micros } => {
+                self.set_rakeback(rakeback_bps, epoch_micros).await;
+            }
+            Operation::SetJackpotConfig { fee_share_bps, win_streak } => {
+                self.set_jackpot_config(fee_share_bps, win_streak).await;
+            }
+            Operation::SetReferralFeeShare { fee_share_bps } => {
+                self.set_referral_fee_share(fee_share_bps).await;
+            }
+            Operation::BlockAccount { account, reason } => {
+                self.block_account(account, reason).await;
+            }
+            Operation::UnblockAccount { account } => {
+                self.unblock_account(account).await;
+            }
             Operation::SetPaused { paused } => {
                 self.set_paused(paused).await;
             }
@@ -156,18 +234,46 @@ impl Contract for SpeedBetContract {
             Operation::Withdraw { amount } => {
                 self.withdraw(amount).await;
             }
+            Operation::SelfExclude { until } => {
+                self.self_exclude(until).await;
+            }
+            Operation::SetGamingLimit { kind, period, amount } => {
+                self.set_gaming_limit(kind, period, amount).await;
+            }
+            Operation::ClaimRakeback => {
+                self.claim_rakeback().await;
+            }
+            Operation::RegisterReferrer { referrer } => {
+                self.register_referrer(referrer).await;
+            }
 
             // Matchmaking operations
-            Operation::JoinQueue { asset, bet_amount } => {
-                self.join_queue(asset, bet_amount).await;
+            Operation::JoinQueue { asset, bet_amount, duration_micros, team_size, mode, versus_asset } => {
+                let entry = QueueEntry {
+                    player: self.signer(),
+                    asset,
+                    bet_amount,
+                    duration_micros,
+                    team_size,
+                    mode,
+                    versus_asset,
+                    joined_at: self.now(),
+                };
+                self.join_queue(entry).await;
             }
             Operation::LeaveQueue => {
                 self.leave_queue().await;
             }
 
             // Duel operations
-            Operation::SubmitPrediction { duel_id, direction } => {
-                self.submit_prediction(duel_id, direction).await;
+            Operation::SubmitPrediction { duel_id, prediction } => {
+                self.submit_prediction(duel_id, prediction).await;
+            }
+            Operation::CommitGuess { duel_id, commitment } => {
+                self.commit_guess(duel_id, commitment).await;
+            }
+            Operation::RevealGuess { duel_id, target, salt } => {
+                self.reveal_guess(duel_id, target, salt).await;
             }
             Operation::StartDuel { duel_id, start_price } => {
                 self.start_duel(duel_id, start_price).await;
@@ -176,7 +282,69 @@ impl Contract for SpeedBetContract {
                 self.resolve_duel(duel_id, end_price).await;
             }
             Operation::CancelDuel { duel_id, reason } => {
-                self.cancel_duel(duel_id, reason).await;
+                self.request_cancel_duel(duel_id, reason).await;
+            }
+            Operation::OfferRematch { duel_id, double_stake } => {
+                self.offer_rematch(duel_id, double_stake).await;
+            }
+            Operation::AcceptRematch { duel_id } => {
+                self.accept_rematch(duel_id).await;
+            }
+            Operation::PlaceSideBet { duel_id, team, amount } => {
+                self.place_side_bet(duel_id, team, amount).await;
+            }
+
+            // Series operations
+            Operation::ChallengeSeries { opponent, asset, stake, best_of, duration_micros } => {
+                self.challenge_series(opponent, asset, stake, best_of, duration_micros).await;
+            }
+            Operation::AcceptSeries { series_id } => {
+                self.accept_series(series_id).await;
+            }
+            Operation::CancelSeries { series_id } => {
+                self.cancel_series(series_id).await;
+            }
+
+            // Tournament operations
+            Operation::CreateTournament { asset, entry_fee, duration_micros, size, prize_split } => {
+                self.create_tournament(asset, entry_fee, duration_micros, size, prize_split).await;
+            }
+            Operation::CancelTournament { tournament_id } => {
+                self.cancel_tournament(tournament_id).await;
+            }
+            Operation::RegisterTournament { tournament_id } => {
+                self.register_tournament(tournament_id).await;
+            }
+
+            // Pool operations
+            Operation::SetPoolInterval { interval_micros } => {
+                self.set_pool_interval(interval_micros).await;
+            }
+            Operation::JoinPool { asset, direction, amount } => {
+                self.join_pool(asset, direction, amount).await;
+            }
+            Operation::LockPoolRound { round_id } => {
+                self.lock_pool_round(round_id).await;
+            }
+            Operation::ResolvePoolRound { round_id } => {
+                self.resolve_pool_round(round_id).await;
+            }
+
+            // House operations
+            Operation::SetHouseConfig { enabled, match_after_micros } => {
+                self.set_house_config(enabled, match_after_micros).await;
+            }
+            Operation::SetHouseExposureLimit { asset, limit } => {
+                self.set_house_exposure_limit(asset, limit).await;
+            }
+            Operation::ProvideLiquidity { amount } => {
+                self.provide_liquidity(amount).await;
+            }
+            Operation::WithdrawLiquidity { shares } => {
+                self.withdraw_liquidity(shares).await;
+            }
+            Operation::MatchWithHouse => {
+                self.match_with_house().await;
             }
 
             // Oracle operations
@@ -205,7 +373,7 @@ impl Contract for SpeedBetContract {
             Message::DuelCancelled { duel_id, reason } => {
                 self.handle_duel_cancelled(duel_id, reason).await;
             }
-            Message::FundsDeposited { player, amount } => {
+            Message::FundsDeposited { player: _, amount: _ } => {
                 // Debug log removed
             }
         }
@@ -253,6 +421,167 @@ impl SpeedBetContract {
         }
     }
 
+    /// Look up a listed asset that accepts new bets.
+    ///
+    /// # Panics
+    /// Panics if the asset is not in the registry or is disabled.
+    async fn check_asset_enabled(&self, asset: &Asset) -> AssetConfig {
+        let config = self.state.get_asset_config(asset).await
+            .unwrap_or_else(|| panic!("Unsupported asset: {}", asset));
+        assert!(config.enabled, "Asset is disabled: {}", asset);
+        self.check_asset_not_halted(asset).await;
+        config
+    }
+
+    /// Check that no circuit breaker has halted the asset.
+    ///
+    /// # Panics
+    /// Panics if the asset is halted.
+    async fn check_asset_not_halted(&self, asset: &Asset) {
+        if let Some(halt) = self.state.get_asset_halt(asset).await {
+            panic!("Asset is halted: {} ({})", asset, halt.reason);
+        }
+    }
+
+    /// Check a bet amount against the asset's limits.
+    ///
+    /// # Panics
+    /// Panics if the asset is not enabled or the amount is outside its bet range.
+    async fn check_asset_bet(&self, asset: &Asset, amount: Amount) -> AssetConfig {
+        let config = self.check_asset_enabled(asset).await;
+        assert!(
+            config.accepts_bet(amount),
+            "Bet must be between {:?} and {:?}",
+            config.min_bet, config.max_bet
+        );
+        config
+    }
+
+    /// Check that the admin has not blocked an account.
+    ///
+    /// # Panics
+    /// Panics if the account is blocked.
+    async fn check_not_blocked(&self, account: &AccountOwner) {
+        if let Some(block) = self.state.get_account_block(account).await {
+            panic!("Account is blocked: {}", block.reason);
+        }
+    }
+
+    /// Check that a player may place new bets or be matched.
+    ///
+    /// # Panics
+    /// Panics if the player is blocked or self-excluded.
+    async fn check_player_eligible(&mut self, player: &AccountOwner) {
+        self.check_not_blocked(player).await;
+        if let Some(until) = self.state.get_self_exclusion(player).await {
+            assert!(self.now() >= until, "Self-excluded until {:?}", until);
+        }
+    }
+
+    /// Whether a player is neither blocked nor self-excluded.
+    async fn is_player_eligible(&mut self, player: &AccountOwner) -> bool {
+        let now = self.now();
+        self.state.get_account_block(player).await.is_none()
+            && self.state.get_self_exclusion(player).await.is_none_or(|until| now >= until)
+    }
+
+    /// Count a queue join or leave against the player's rate limit.
+    ///
+    /// # Panics
+    /// Panics if the player has used up the current window.
+    async fn check_queue_rate_limit(&mut self, player: &AccountOwner) {
+        let now = self.now();
+        let max_ops = *self.state.queue_rate_limit_ops.get();
+        let window_micros = *self.state.queue_rate_limit_window_micros.get();
+
+        let mut activity = self.state.get_queue_activity(player).await;
+        assert!(
+            activity.record(now, window_micros, max_ops),
+            "Rate limit exceeded: at most {} queue joins and leaves per {} micros",
+            max_ops, window_micros
+        );
+        self.state.queue_activity.insert(player, activity).unwrap();
+    }
+
+    /// Check that `amount` more of a kind stays within the player's
+    /// responsible-gaming limits, applying any limit change now due.
+    ///
+    /// # Panics
+    /// Panics if any of the player's limits on the kind would be exceeded.
+    async fn check_gaming_limit(&mut self, player: &AccountOwner, kind: LimitKind, amount: Amount) {
+        let now = self.now();
+        let mut limits = self.state.get_player_limits(player).await;
+        limits.apply_pending(now);
+        assert!(limits.allows(kind, amount, now), "{:?} limit reached", kind);
+        self.state.update_player_limits(player, limits).await;
+    }
+
+    /// Check a new stake against the player's wager and loss limits.
+    ///
+    /// # Panics
+    /// Panics if the stake would exceed either limit.
+    async fn check_stake_limits(&mut self, player: &AccountOwner, amount: Amount) {
+        // The whole stake counts towards wager limits and could count towards loss limits
+        self.check_gaming_limit(player, LimitKind::Wager, amount).await;
+        self.check_gaming_limit(player, LimitKind::Loss, amount).await;
+    }
+
+    /// Count an amount towards the player's responsible-gaming limits.
+    async fn record_gaming_activity(&mut self, player: &AccountOwner, kind: LimitKind, amount: Amount) {
+        if *player == HOUSE_ACCOUNT || amount == Amount::ZERO {
+            return;
+        }
+        let now = self.now();
+        let mut limits = self.state.get_player_limits(player).await;
+        limits.record(kind, amount, now);
+        self.state.update_player_limits(player, limits).await;
+    }
+
+    /// Rescale a reported price to the asset's stored precision.
+    ///
+    /// # Panics
+    /// Panics if the asset is not in the registry.
+    async fn normalize_price(&self, asset: &Asset, price: Price) -> Price {
+        let config = self.state.get_asset_config(asset).await
+            .unwrap_or_else(|| panic!("Unsupported asset: {}", asset));
+        price.with_decimals(config.price_decimals)
+    }
+
+    /// Get the quote in effect at `at`, unless it is older than the asset's
+    /// maximum staleness.
+    async fn fresh_price_at(&self, asset: &Asset, at: Timestamp) -> Option<PriceData> {
+        let max_staleness = self.state.get_asset_config(asset).await
+            .and_then(|config| config.max_staleness_micros);
+        self.state.price_at(asset, at).await.filter(|quote| {
+            let age = at.micros().saturating_sub(quote.timestamp.micros());
+            max_staleness.is_none_or(|max| age <= max)
+        })
+    }
+
+    /// Normalize a caller-supplied price and hold it to the asset's circuit
+    /// breaker against the oracle quote in effect at `at`.
+    ///
+    /// # Panics
+    /// Panics if the asset has a deviation limit and there is no fresh quote
+    /// to check against, or the price moves further from it than allowed.
+    async fn check_manual_price(&self, asset: &Asset, price: Price, at: Timestamp) -> Price {
+        let config = self.state.get_asset_config(asset).await
+            .unwrap_or_else(|| panic!("Unsupported asset: {}", asset));
+        let price = price.with_decimals(config.price_decimals);
+
+        if let Some(max_deviation_bps) = config.max_deviation_bps {
+            let reference = self.fresh_price_at(asset, at).await
+                .expect("No fresh oracle price to check the supplied price against");
+            let deviation = price.deviation_bps(&reference.price);
+            assert!(
+                deviation <= u128::from(max_deviation_bps),
+                "Supplied price moves {} bps from oracle price {}",
+                deviation, reference.price
+            );
+        }
+        price
+    }
+
     // --- ADMIN METHODS ---
 
     async fn update_settings(
@@ -280,341 +609,2185 @@ impl SpeedBetContract {
         );
     }
 
-    async fn set_paused(&mut self, paused: bool) {
+    async fn set_fee_tiers(&mut self, tiers: Vec<FeeTier>) {
         self.check_admin().await;
-        self.state.paused.set(paused);
-        log::info!("Platform paused: {}", paused);
-    }
-
-    // --- PLAYER METHODS ---
-
-    async fn deposit(&mut self, amount: Amount) {
-        let player = self.signer();
-
-        // In production, this would involve token transfers
-        // For MVP, we just credit the balance
-        self.state.credit(&player, amount).await;
+        assert!(
+            tiers.windows(2).all(|pair| pair[0].min_wagered < pair[1].min_wagered),
+            "Fee tiers must have increasing thresholds"
+        );
+        assert!(tiers.iter().all(|tier| tier.fee_bps <= 1000), "Fee cannot exceed 10%");
 
-        log::info!("Player {:?} deposited {:?}", player, amount);
+        log::info!("Fee tiers set: {:?}", tiers);
+        self.state.fee_tiers.set(tiers);
     }
 
-    async fn withdraw(&mut self, amount: Amount) {
-        let player = self.signer();
-
-        self.state.debit(&player, amount).await
-            .expect("Insufficient balance for withdrawal");
-
-        // In production, this would involve token transfers
+    async fn set_range_buckets(&mut self, edge_bps: u64, payout_bps: Vec<u16>) {
+        self.check_admin().await;
+        assert!(edge_bps > 0, "Bucket edge must be positive");
+        assert!(!payout_bps.is_empty(), "At least one payout tier is required");
+        assert!(
+            payout_bps.iter().all(|bps| (1..=10000).contains(bps)),
+            "Payout tiers must be between 1 and 10000 bps"
+        );
+        assert!(
+            payout_bps.windows(2).all(|pair| pair[0] >= pair[1]),
+            "Closer calls cannot pay less"
+        );
 
-        log::info!("Player {:?} withdrew {:?}", player, amount);
+        log::info!("Range buckets set: edge={} bps, payouts={:?}", edge_bps, payout_bps);
+        self.state.range_buckets.set(RangeBuckets { edge_bps, payout_bps });
     }
 
-    // --- MATCHMAKING METHODS ---
+    async fn set_duel_durations(&mut self, durations: Vec<u64>) {
+        self.check_admin().await;
+        assert!(!durations.is_empty(), "At least one duel duration is required");
+        assert!(durations.iter().all(|d| *d > 0), "Duel durations must be positive");
+        log::info!("Duel durations set to {:?}", durations);
+        self.state.duel_durations.set(durations);
+    }
 
-    async fn join_queue(&mut self, asset: Asset, bet_amount: Amount) {
-        self.check_not_paused().await;
-        let player = self.signer();
-        let now = self.now();
+    async fn list_asset(&mut self, mut config: AssetConfig) {
+        self.check_admin().await;
 
-        // Check if player is already in queue (double-join prevention)
+        assert!(!config.asset.symbol().is_empty(), "Asset symbol cannot be empty");
+        assert!(config.min_bet <= config.max_bet, "Minimum bet cannot exceed maximum bet");
         assert!(
-            !self.state.is_in_queue(&player).await,
-            "Already in queue"
+            config.price_decimals <= MAX_PRICE_DECIMALS,
+            "Price decimals cannot exceed {}",
+            MAX_PRICE_DECIMALS
         );
 
-        // Validate asset is supported
-        match asset {
-            Asset::BTC | Asset::ETH => {} // Supported assets
-            #[allow(unreachable_patterns)]
-            _ => panic!("Unsupported asset type: {:?}", asset),
-        }
-
-        // Validate bet amount
-        let min_bet = *self.state.min_bet.get();
-        let max_bet = *self.state.max_bet.get();
-        assert!(
-            bet_amount >= min_bet && bet_amount <= max_bet,
-            "Bet must be between {:?} and {:?}",
-            min_bet, max_bet
+        log::info!(
+            "Asset listed: {} quoted in {} (price_decimals={}, min_bet={:?}, max_bet={:?}, oracle_sources={:?})",
+            config.asset,
+            config.quote_currency,
+            config.price_decimals,
+            config.min_bet,
+            config.max_bet,
+            config.oracle_sources
         );
 
-        // Check player has sufficient balance
-        let balance = self.state.get_balance(&player).await;
-        assert!(balance >= bet_amount, "Insufficient balance");
+        // Re-listing keeps the configured circuit breaker and house limit
+        if let Some(existing) = self.state.get_asset_config(&config.asset).await {
+            config.max_deviation_bps = existing.max_deviation_bps;
+            config.max_staleness_micros = existing.max_staleness_micros;
+            config.house_exposure_limit = existing.house_exposure_limit;
+        }
+        self.state.set_asset_config(config).await;
+    }
 
-        // Try to find a match
-        if let Some(opponent_entry) = self.state.find_match(&player, asset, bet_amount).await {
-            // Match found! Create duel
-            let duel_id = self.state.next_duel_id().await;
+    async fn set_asset_enabled(&mut self, asset: Asset, enabled: bool) {
+        self.check_admin().await;
 
-            // Debit both players
-            self.state.debit(&opponent_entry.player, bet_amount).await.unwrap();
-            self.state.debit(&player, bet_amount).await.unwrap();
+        let mut config = self.state.get_asset_config(&asset).await
+            .unwrap_or_else(|| panic!("Unsupported asset: {}", asset));
+        config.enabled = enabled;
+        self.state.set_asset_config(config).await;
 
-            // Create duel
-            let duel = DuelInfo::new(
-                duel_id,
-                opponent_entry.player,  // First in queue is player1
-                player,                  // Joiner is player2
-                asset,
-                bet_amount,
-                now,
-            );
+        log::info!("Asset {} enabled: {}", asset, enabled);
+    }
 
-            self.state.add_duel(duel.clone()).await;
+    async fn set_settlement_mode(&mut self, duel_mode: DuelMode, mode: SettlementMode) {
+        self.check_admin().await;
 
-            log::info!(
-                "Duel {:?} created between player1={:?} and player2={:?} for {:?} with bet {:?}",
-                duel_id,
-                opponent_entry.player,
-                player,
-                asset,
-                bet_amount
-            );
+        if let SettlementMode::Twap { window_micros } = mode {
+            assert!(window_micros > 0, "TWAP window must be positive");
+        }
 
-            // Record stats
-            let mut stats1 = self.state.get_stats(&opponent_entry.player).await;
-            let mut stats2 = self.state.get_stats(&player).await;
-            stats1.record_bet(bet_amount);
-            stats2.record_bet(bet_amount);
-            self.state.update_stats(&opponent_entry.player, stats1).await;
-            self.state.update_stats(&player, stats2).await;
+        self.state.settlement_modes.insert(&duel_mode, mode).unwrap();
 
-            // Send notifications to both players
-            let chain_id = self.runtime.chain_id();
+        log::info!("{:?} duel settlement mode: {:?}", duel_mode, mode);
+    }
 
-            self.runtime
-                .prepare_message(Message::MatchFound {
-                    duel_id,
-                    opponent: player,
-                    asset,
-                    bet_amount,
-                    duel_chain: chain_id,
-                })
-                .with_authentication()
-                .send_to(chain_id); // In production, send to opponent's chain
+    async fn set_circuit_breaker(
+        &mut self,
+        asset: Asset,
+        max_deviation_bps: Option<u64>,
+        max_staleness_micros: Option<u64>,
+    ) {
+        self.check_admin().await;
 
-            self.runtime
-                .prepare_message(Message::MatchFound {
-                    duel_id,
-                    opponent: opponent_entry.player,
-                    asset,
-                    bet_amount,
-                    duel_chain: chain_id,
-                })
-                .with_authentication()
-                .send_to(chain_id); // In production, send to joiner's chain
+        assert!(max_deviation_bps != Some(0), "Maximum deviation must be positive");
+        assert!(max_staleness_micros != Some(0), "Maximum staleness must be positive");
 
-        } else {
-            // No match, add to queue
-            let entry = QueueEntry {
-                player,
-                asset,
-                bet_amount,
-                joined_at: now,
-            };
-            self.state.add_to_queue(entry).await;
+        let mut config = self.state.get_asset_config(&asset).await
+            .unwrap_or_else(|| panic!("Unsupported asset: {}", asset));
+        config.max_deviation_bps = max_deviation_bps;
+        config.max_staleness_micros = max_staleness_micros;
+        self.state.set_asset_config(config).await;
 
-            log::info!(
-                "Player {:?} joined queue for {:?} with bet {:?}",
-                player,
-                asset,
-                bet_amount
-            );
-        }
+        log::info!(
+            "Asset {} circuit breaker: max_deviation_bps={:?}, max_staleness_micros={:?}",
+            asset, max_deviation_bps, max_staleness_micros
+        );
     }
 
-    async fn leave_queue(&mut self) {
-        let player = self.signer();
+    async fn reset_circuit_breaker(&mut self, asset: Asset) {
+        self.check_admin().await;
 
-        let removed = self.state.remove_from_queue(&player).await;
-        assert!(removed, "Player not in queue");
+        assert!(
+            self.state.get_asset_halt(&asset).await.is_some(),
+            "Asset is not halted: {}",
+            asset
+        );
+        self.state.clear_asset_halt(&asset).await;
 
-        log::info!("Player {:?} left queue", player);
+        log::info!("Circuit breaker reset for {}", asset);
     }
 
-    // --- DUEL METHODS ---
-
-    async fn submit_prediction(&mut self, duel_id: DuelId, direction: Direction) {
-        self.check_not_paused().await;
-        let player = self.signer();
+    async fn clear_duel(&mut self, duel_id: DuelId) {
+        self.check_admin().await;
 
-        // Get duel
         let mut duel = self.state.get_duel(duel_id).await
             .expect("Duel not found");
+        assert!(duel.status == DuelStatus::PendingReview, "Duel is not pending review");
 
-        // Validate state
-        assert!(
-            duel.status == DuelStatus::WaitingForPredictions,
-            "Cannot predict: duel not waiting for predictions"
-        );
-
-        // Validate participant
-        assert!(duel.is_participant(&player), "Not a participant");
+        // The duel settles normally once its asset is no longer halted
+        duel.status = DuelStatus::Active;
+        self.state.update_duel(duel).await;
 
-        // Set prediction
-        let success = duel.set_prediction(&player, direction);
-        assert!(success, "Already predicted");
+        log::info!("Duel {:?} cleared after review", duel_id);
+    }
 
-        // Log prediction
-        log::info!(
-            "Player {:?} predicted {:?} for duel {:?}",
-            player,
-            direction,
-            duel_id
-        );
+    async fn void_duel(&mut self, duel_id: DuelId) {
+        self.check_admin().await;
 
-        // Update duel
-        self.state.update_duel(duel.clone()).await;
+        let duel = self.state.get_duel(duel_id).await
+            .expect("Duel not found");
+        assert!(duel.status == DuelStatus::PendingReview, "Duel is not pending review");
 
-        // Check if both have predicted
-        if duel.both_predicted() {
-            log::info!("Duel {:?}: Both players have predicted", duel_id);
-            // Frontend will call StartDuel with current price
-        }
+        self.cancel_duel(duel_id, "Voided after circuit breaker review".to_string()).await;
     }
 
-    async fn start_duel(&mut self, duel_id: DuelId, start_price: u64) {
-        let mut duel = self.state.get_duel(duel_id).await
-            .expect("Duel not found");
+    async fn set_queue_rate_limit(&mut self, max_ops: u32, window_micros: u64) {
+        self.check_admin().await;
+        assert!(max_ops > 0, "Rate limit must allow at least one operation");
+        assert!(window_micros > 0, "Rate-limit window must be positive");
+        self.state.queue_rate_limit_ops.set(max_ops);
+        self.state.queue_rate_limit_window_micros.set(window_micros);
+        log::info!("Queue rate limit set to {} operations per {} micros", max_ops, window_micros);
+    }
 
+    async fn set_collusion_limits(
+        &mut self,
+        window_micros: u64,
+        flag_threshold: u32,
+        max_pair_matches: Option<u32>,
+        opposite_ratio_bps: Option<u16>,
+    ) {
+        self.check_admin().await;
+        assert!(window_micros > 0, "Pair window must be positive");
+        assert!(flag_threshold > 0, "Flag threshold must be positive");
+        assert!(max_pair_matches != Some(0), "Pair match limit must be positive");
         assert!(
-            duel.status == DuelStatus::WaitingForPredictions,
-            "Duel not in correct state"
+            opposite_ratio_bps.is_none_or(|bps| (1..=10000).contains(&bps)),
+            "Opposite prediction ratio must be between 1 and 10000 bps"
         );
-        assert!(duel.both_predicted(), "Both players must predict first");
+        self.state.pair_window_micros.set(window_micros);
+        self.state.pair_flag_threshold.set(flag_threshold);
+        self.state.pair_match_limit.set(max_pair_matches);
+        self.state.pair_opposite_ratio_bps.set(opposite_ratio_bps);
+        log::info!(
+            "Collusion limits set: window={} micros, flag at {}, match limit {:?}, opposite ratio {:?} bps",
+            window_micros, flag_threshold, max_pair_matches, opposite_ratio_bps
+        );
+    }
 
-        // Lock in start price and begin timer
-        duel.start_price = Some(start_price);
-        duel.started_at = Some(self.now());
-        duel.status = DuelStatus::Active;
+    async fn clear_pair_flag(&mut self, player_a: AccountOwner, player_b: AccountOwner) {
+        self.check_admin().await;
+        let mut stats = self.state.get_pair_stats(&player_a, &player_b).await;
+        assert!(stats.flagged_at.is_some(), "Pair is not flagged");
+        stats.flagged_at = None;
+        self.state.update_pair_stats(&player_a, &player_b, stats).await;
+        log::info!("Cleared flag on pair {:?} / {:?}", player_a, player_b);
+    }
 
-        self.state.update_duel(duel).await;
+    async fn set_price_history_length(&mut self, length: u32) {
+        self.check_admin().await;
+        assert!(
+            (1..=MAX_PRICE_HISTORY_LEN).contains(&length),
+            "Price history length must be between 1 and {}",
+            MAX_PRICE_HISTORY_LEN
+        );
+        self.state.price_history_len.set(length);
+        log::info!("Price history length set to {}", length);
+    }
 
-        log::info!("Duel {:?} started at price {}", duel_id, start_price);
+    async fn set_rakeback(&mut self, rakeback_bps: u16, epoch_micros: u64) {
+        self.check_admin().await;
+        assert!(epoch_micros > 0, "Rakeback epoch must be positive");
+        self.check_fee_shares(
+            *self.state.referral_fee_share_bps.get(),
+            *self.state.jackpot_fee_share_bps.get(),
+            rakeback_bps,
+        );
+        self.state.rakeback_bps.set(rakeback_bps);
+        self.state.rakeback_epoch_micros.set(epoch_micros);
+        log::info!("Rakeback set to {} bps per {} micro epoch", rakeback_bps, epoch_micros);
     }
 
-    async fn resolve_duel(&mut self, duel_id: DuelId, end_price: u64) {
-        let mut duel = self.state.get_duel(duel_id).await
-            .expect("Duel not found");
+    async fn set_jackpot_config(&mut self, fee_share_bps: u16, win_streak: u64) {
+        self.check_admin().await;
+        assert!(win_streak > 0, "Jackpot win streak must be positive");
+        self.check_fee_shares(
+            *self.state.referral_fee_share_bps.get(),
+            fee_share_bps,
+            *self.state.rakeback_bps.get(),
+        );
+        self.state.jackpot_fee_share_bps.set(fee_share_bps);
+        self.state.jackpot_win_streak.set(win_streak);
+        log::info!(
+            "Jackpot fed {} bps of fees, won on a {} duel win streak",
+            fee_share_bps, win_streak
+        );
+    }
+
+    async fn set_referral_fee_share(&mut self, fee_share_bps: u16) {
+        self.check_admin().await;
+        self.check_fee_shares(
+            fee_share_bps,
+            *self.state.jackpot_fee_share_bps.get(),
+            *self.state.rakeback_bps.get(),
+        );
+        self.state.referral_fee_share_bps.set(fee_share_bps);
+        log::info!("Referral fee share set to {} bps", fee_share_bps);
+    }
 
+    /// Check that the referral, jackpot and rakeback shares fit in the fee.
+    ///
+    /// # Panics
+    /// Panics if together they exceed 100% of the fee.
+    fn check_fee_shares(&self, referral_bps: u16, jackpot_bps: u16, rakeback_bps: u16) {
+        let total = u32::from(referral_bps) + u32::from(jackpot_bps) + u32::from(rakeback_bps);
         assert!(
-            duel.status == DuelStatus::Active,
-            "Duel not active"
+            total <= 10000,
+            "Referral, jackpot and rakeback shares cannot exceed 100% of fees together"
+        );
+    }
+
+    async fn block_account(&mut self, account: AccountOwner, reason: String) {
+        self.check_admin().await;
+        assert!(account != HOUSE_ACCOUNT, "Cannot block the house");
+        assert!(
+            self.state.get_account_block(&account).await.is_none(),
+            "Account already blocked"
         );
 
-        // Set end price and determine winner
-        duel.end_price = Some(end_price);
-        let winner = duel.determine_winner().expect("Could not determine winner");
-        let loser = if winner == duel.player1 { duel.player2 } else { duel.player1 };
+        let block = AccountBlock {
+            reason: reason.clone(),
+            blocked_by: self.signer(),
+            blocked_at: self.now(),
+        };
+        self.state.blocked_accounts.insert(&account, block).unwrap();
 
-        // Calculate payout with platform fee
-        let fee_bps = *self.state.fee_bps.get();
-        let total_pot = duel.total_pot();
+        // A blocked account cannot be matched
+        self.state.remove_from_queue(&account).await;
+
+        log::info!("Account {:?} blocked: {}", account, reason);
+    }
+
+    async fn unblock_account(&mut self, account: AccountOwner) {
+        self.check_admin().await;
+        assert!(
+            self.state.get_account_block(&account).await.is_some(),
+            "Account is not blocked"
+        );
+        self.state.blocked_accounts.remove(&account).unwrap();
+        log::info!("Account {:?} unblocked", account);
+    }
 
-        // Calculate fee: fee = total_pot * fee_bps / 10000
-        let fee_micros = (u128::from(total_pot) * u128::from(fee_bps) / 10000) as u64;
-        let fee = Amount::from_micros(fee_micros.into());
-        let payout = total_pot.saturating_sub(fee);
+    async fn set_paused(&mut self, paused: bool) {
+        self.check_admin().await;
+        self.state.paused.set(paused);
+        log::info!("Platform paused: {}", paused);
+    }
 
-        // Credit winner
-        self.state.credit(&winner, payout).await;
+    // --- PLAYER METHODS ---
 
-        // Update stats
-        let mut winner_stats = self.state.get_stats(&winner).await;
-        let mut loser_stats = self.state.get_stats(&loser).await;
-        winner_stats.record_win(payout);
-        loser_stats.record_loss();
-        self.state.update_stats(&winner, winner_stats).await;
-        self.state.update_stats(&loser, loser_stats).await;
+    async fn deposit(&mut self, amount: Amount) {
+        let player = self.signer();
 
-        // Record volume
-        self.state.record_volume(total_pot, fee).await;
+        self.check_gaming_limit(&player, LimitKind::Deposit, amount).await;
 
-        // Complete duel
-        self.state.complete_duel(duel.clone()).await;
+        // In production, this would involve token transfers
+        // For MVP, we just credit the balance
+        self.state.credit(&player, amount).await;
+        self.record_gaming_activity(&player, LimitKind::Deposit, amount).await;
 
-        log::info!(
-            "Duel {:?} resolved: winner={:?}, loser={:?}, payout={:?}, fee={:?}",
-            duel_id, winner, loser, payout, fee
+        log::info!("Player {:?} deposited {:?}", player, amount);
+    }
+
+    async fn withdraw(&mut self, amount: Amount) {
+        let player = self.signer();
+
+        self.state.debit(&player, amount).await
+            .expect("Insufficient balance for withdrawal");
+
+        // In production, this would involve token transfers
+
+        log::info!("Player {:?} withdrew {:?}", player, amount);
+    }
+
+    async fn self_exclude(&mut self, until: Timestamp) {
+        let player = self.signer();
+        let now = self.now();
+
+        assert!(until > now, "Self-exclusion must end in the future");
+        if let Some(current) = self.state.get_self_exclusion(&player).await {
+            assert!(until >= current, "Self-exclusion cannot be shortened");
+        }
+        self.state.self_excluded_until.insert(&player, until).unwrap();
+
+        // An excluded player cannot be matched
+        self.state.remove_from_queue(&player).await;
+
+        log::info!("Player {:?} self-excluded until {:?}", player, until);
+    }
+
+    async fn set_gaming_limit(&mut self, kind: LimitKind, period: LimitPeriod, amount: Option<Amount>) {
+        let player = self.signer();
+        let now = self.now();
+
+        let mut limits = self.state.get_player_limits(&player).await;
+        limits.set_limit(kind, period, amount, now);
+        self.state.update_player_limits(&player, limits).await;
+
+        log::info!("Player {:?} set {:?} {:?} limit to {:?}", player, period, kind, amount);
+    }
+
+    async fn claim_rakeback(&mut self) {
+        let player = self.signer();
+        let now = self.now();
+        let epoch_micros = *self.state.rakeback_epoch_micros.get();
+
+        // Epochs are fixed slices of system time, claimable once each
+        let epoch = now.micros() / epoch_micros;
+        let mut account = self.state.get_rakeback_account(&player).await;
+        assert!(
+            account.last_claimed_epoch.is_none_or(|last| epoch > last),
+            "Rakeback already claimed this epoch"
         );
 
-        // Send payout message
-        self.runtime
-            .prepare_message(Message::Payout {
-                duel_id,
-                amount: payout,
-            })
-            .with_authentication()
-            .send_to(self.runtime.chain_id());
+        let fees = account.unclaimed_fees;
+        let amount = account.accrued;
+        assert!(amount > Amount::ZERO, "No rakeback to claim");
+
+        account.unclaimed_fees = Amount::ZERO;
+        account.accrued = Amount::ZERO;
+        account.last_claimed_at = Some(now);
+        account.last_claimed_epoch = Some(epoch);
+        self.state.update_rakeback_account(&player, account).await;
+        self.state.credit(&player, amount).await;
+        self.state.record_rakeback_claim(RakebackClaim {
+            player,
+            fees,
+            amount,
+            claimed_at: now,
+        }).await;
+
+        log::info!("Player {:?} claimed {:?} rakeback on {:?} fees", player, amount, fees);
     }
 
-    async fn cancel_duel(&mut self, duel_id: DuelId, reason: String) {
-        let mut duel = self.state.get_duel(duel_id).await
-            .expect("Duel not found");
+    async fn register_referrer(&mut self, referrer: AccountOwner) {
+        let player = self.signer();
 
+        assert!(referrer != player, "Cannot refer yourself");
+        assert!(referrer != HOUSE_ACCOUNT, "The house cannot be a referrer");
         assert!(
-            duel.status != DuelStatus::Resolved,
-            "Cannot cancel resolved duel"
+            self.state.get_referrer(&player).await.is_none(),
+            "Referrer already registered"
+        );
+        assert!(
+            self.state.get_referrer(&referrer).await != Some(player),
+            "Cannot refer your own referrer"
         );
 
-        // Refund both players
-        self.state.credit(&duel.player1, duel.bet_amount).await;
-        self.state.credit(&duel.player2, duel.bet_amount).await;
+        self.state.referrers.insert(&player, referrer).unwrap();
 
-        duel.status = DuelStatus::Cancelled;
-        self.state.complete_duel(duel.clone()).await;
+        log::info!("Player {:?} registered referrer {:?}", player, referrer);
+    }
 
-        log::info!("Duel {:?} cancelled: {}", duel_id, reason);
+    // --- MATCHMAKING METHODS ---
 
-        // Send refund messages
-        self.runtime
-            .prepare_message(Message::Refund {
+    async fn join_queue(&mut self, entry: QueueEntry) {
+        self.check_not_paused().await;
+        let player = entry.player;
+        let now = entry.joined_at;
+        self.check_player_eligible(&player).await;
+        self.check_queue_rate_limit(&player).await;
+
+        // Check if player is already in queue (double-join prevention)
+        assert!(
+            !self.state.is_in_queue(&player).await,
+            "Already in queue"
+        );
+
+        // Validate asset and bet amount
+        self.check_asset_bet(&entry.asset, entry.bet_amount).await;
+
+        // Relative duels, and only those, compare against a second asset
+        assert!(
+            (entry.mode == DuelMode::Relative) == entry.versus_asset.is_some(),
+            "Relative duels need a versus asset and other modes take none"
+        );
+        if let Some(versus_asset) = &entry.versus_asset {
+            assert!(*versus_asset != entry.asset, "Versus asset must differ from the duel asset");
+            self.check_asset_enabled(versus_asset).await;
+        }
+
+        // Validate duration
+        assert!(
+            self.state.is_allowed_duration(entry.duration_micros),
+            "Unsupported duel duration: {} micros",
+            entry.duration_micros
+        );
+
+        assert!(
+            (1..=MAX_TEAM_SIZE).contains(&entry.team_size),
+            "Team size must be between 1 and {}",
+            MAX_TEAM_SIZE
+        );
+
+        // Check player has sufficient balance
+        let balance = self.state.get_balance(&player).await;
+        assert!(balance >= entry.bet_amount, "Insufficient balance");
+
+        self.check_stake_limits(&player, entry.bet_amount).await;
+
+        // Try to find a match
+        if let Some(matched) = self.state.find_match(&entry).await {
+            // Match found! Earliest in queue lead the teams, joiner is on team two
+            let mut players: Vec<_> = matched.iter().map(|e| e.player).collect();
+            players.push(player);
+            let (team_one, team_two) = players.split_at(usize::from(entry.team_size));
+
+            let duel_id = self.state.next_duel_id().await;
+            let mut duel = DuelInfo::new(
                 duel_id,
-                amount: duel.bet_amount,
-            })
-            .with_authentication()
-            .send_to(self.runtime.chain_id());
+                team_one[0],
+                team_two[0],
+                entry.asset,
+                entry.bet_amount,
+                entry.duration_micros,
+                now,
+            );
+            duel.set_teams(team_one, team_two);
+            duel.mode = entry.mode;
+            duel.versus_asset = entry.versus_asset;
+            self.create_duel(duel).await;
+        } else {
+            // No match, add to queue
+            log::info!(
+                "Player {:?} joined queue for {} with bet {:?}, duration {}, team size {}, mode {:?} and versus {:?}",
+                player,
+                entry.asset,
+                entry.bet_amount,
+                entry.duration_micros,
+                entry.team_size,
+                entry.mode,
+                entry.versus_asset
+            );
+
+            self.state.add_to_queue(entry).await;
+        }
     }
 
-    // --- ORACLE METHODS ---
+    /// Escrow every stake, store a new duel and notify the participants.
+    async fn create_duel(&mut self, mut duel: DuelInfo) -> DuelId {
+        // The settlement mode of the duel's mode is fixed when the duel is created
+        duel.settlement = self.state.get_settlement_mode(duel.mode).await;
+        // Range duels keep the bucket edge and payouts in force when created
+        if duel.mode == DuelMode::Range {
+            duel.range_buckets = Some(self.state.range_buckets.get().clone());
+        }
 
-    async fn update_price(&mut self, asset: Asset, price: u64) {
-        let price_data = PriceData {
+        let duel_id = duel.id;
+        let player1 = duel.player1;
+        let player2 = duel.player2;
+        let asset = duel.asset.clone();
+        let bet_amount = duel.bet_amount;
+        let participants = duel.participants.clone();
+        let opposing_pairs = duel.opposing_pairs();
+
+        // Debit every participant
+        for participant in &participants {
+            self.state.debit(&participant.player, participant.stake).await.unwrap();
+        }
+
+        self.state.add_duel(duel).await;
+
+        log::info!(
+            "Duel {:?} created between player1={:?} and player2={:?} ({} players) for {} with bet {:?}",
+            duel_id,
+            player1,
+            player2,
+            participants.len(),
             asset,
-            price,
-            timestamp: self.now(),
-        };
+            bet_amount
+        );
 
-        self.state.set_price(price_data).await;
+        // Record stats
+        for participant in &participants {
+            if participant.player == HOUSE_ACCOUNT {
+                continue;
+            }
+            let mut stats = self.state.get_stats(&participant.player).await;
+            stats.record_bet(participant.stake);
+            self.state.update_stats(&participant.player, stats).await;
+            self.record_gaming_activity(&participant.player, LimitKind::Wager, participant.stake).await;
+        }
+        self.record_pair_duels(duel_id, &opposing_pairs).await;
 
-        log::info!("Price updated: {:?} = {}", asset, price);
-    }
+        // Send notifications to every participant, naming the opposing team's lead
+        let chain_id = self.runtime.chain_id();
 
-    // --- MESSAGE HANDLERS ---
+        for participant in &participants {
+            let opponent = match participant.team {
+                Team::One => player2,
+                Team::Two => player1,
+            };
+            self.runtime
+                .prepare_message(Message::MatchFound {
+                    duel_id,
+                    opponent,
+                    asset: asset.clone(),
+                    bet_amount: participant.stake,
+                    duel_chain: chain_id,
+                })
+                .with_authentication()
+                .send_to(chain_id); // In production, send to the participant's chain
+        }
 
-    async fn handle_payout(&mut self, duel_id: DuelId, amount: Amount) {
+        duel_id
+    }
+
+    async fn leave_queue(&mut self) {
         let player = self.signer();
-        self.state.credit(&player, amount).await;
-        log::info!("Payout received for duel {:?}: {:?} to {:?}", duel_id, amount, player);
+        self.check_queue_rate_limit(&player).await;
+
+        let removed = self.state.remove_from_queue(&player).await;
+        assert!(removed, "Player not in queue");
+
+        log::info!("Player {:?} left queue", player);
     }
 
-    async fn handle_refund(&mut self, duel_id: DuelId, amount: Amount) {
+    // --- DUEL METHODS ---
+
+    async fn submit_prediction(&mut self, duel_id: DuelId, prediction: Prediction) {
+        assert!(
+            !matches!(prediction, Prediction::Guess(_)),
+            "Closest-price guesses must be committed with CommitGuess"
+        );
+        self.record_prediction(duel_id, prediction).await;
+    }
+
+    async fn commit_guess(&mut self, duel_id: DuelId, commitment: CryptoHash) {
+        let guess = PriceGuess {
+            commitment,
+            committed_at: self.now(),
+            target: None,
+        };
+        self.record_prediction(duel_id, Prediction::Guess(guess)).await;
+    }
+
+    async fn reveal_guess(&mut self, duel_id: DuelId, target: Price, salt: String) {
         let player = self.signer();
-        self.state.credit(&player, amount).await;
-        log::info!("Refund received for duel {:?}: {:?} to {:?}", duel_id, amount, player);
+        self.check_not_blocked(&player).await;
+        let now = self.now();
+
+        let mut duel = self.state.get_duel(duel_id).await
+            .expect("Duel not found");
+
+        assert!(duel.status == DuelStatus::Active, "Duel not active");
+        let ends_at = duel.ends_at().expect("Duel has not started");
+        assert!(
+            now.micros() <= ends_at.micros().saturating_add(REVEAL_WINDOW_MICROS),
+            "Reveal window has closed"
+        );
+
+        let reveal = GuessReveal { duel_id, target, salt };
+        let target = self.normalize_price(&duel.asset, target).await;
+        let guess = duel.guess_mut(&player).expect("No committed guess for this player");
+        assert!(guess.target.is_none(), "Guess already revealed");
+        assert!(reveal.commitment() == guess.commitment, "Reveal does not match commitment");
+        guess.target = Some(target);
+
+        self.state.update_duel(duel).await;
+
+        log::info!("Player {:?} revealed guess {} for duel {:?}", player, target, duel_id);
     }
 
-    async fn handle_duel_completed(
-        &mut self,
-        duel_id: DuelId,
-        winner: AccountOwner,
-        loser: AccountOwner,
-        pot: Amount,
-    ) {
+    /// Record a player's prediction and note when both are in.
+    async fn record_prediction(&mut self, duel_id: DuelId, prediction: Prediction) {
+        self.check_not_paused().await;
+        let player = self.signer();
+        self.check_not_blocked(&player).await;
+
+        // Get duel
+        let mut duel = self.state.get_duel(duel_id).await
+            .expect("Duel not found");
+
+        // Validate state
+        assert!(
+            duel.status == DuelStatus::WaitingForPredictions,
+            "Cannot predict: duel not waiting for predictions"
+        );
+
+        // Validate participant
+        assert!(duel.is_participant(&player), "Not a participant");
+
+        assert!(
+            prediction.mode() == duel.mode,
+            "Prediction does not match duel mode {:?}",
+            duel.mode
+        );
+
+        // Set prediction
+        let success = duel.set_prediction(&player, prediction);
+        assert!(success, "Already predicted");
+
+        // The house always takes the other side
+        if duel.is_house_duel() {
+            if let Some(opposite) = prediction.opposite() {
+                duel.set_prediction(&HOUSE_ACCOUNT, opposite);
+            }
+        }
+
+        // Log prediction
         log::info!(
-            "Duel {:?} completed: winner={:?}, loser={:?}, pot={:?}",
-            duel_id, winner, loser, pot
+            "Player {:?} predicted {:?} for duel {:?}",
+            player,
+            prediction,
+            duel_id
+        );
+
+        // Update duel
+        self.state.update_duel(duel.clone()).await;
+
+        // Check if both have predicted
+        if duel.both_predicted() {
+            log::info!("Duel {:?}: Both players have predicted", duel_id);
+            // Frontend will call StartDuel with current price
+        }
+    }
+
+    async fn start_duel(&mut self, duel_id: DuelId, start_price: Option<Price>) {
+        let mut duel = self.state.get_duel(duel_id).await
+            .expect("Duel not found");
+        self.check_asset_not_halted(&duel.asset).await;
+        let now = self.now();
+        assert!(
+            start_price.is_none() || duel.mode != DuelMode::Relative,
+            "Relative duels lock prices from oracle state"
+        );
+        let start_price = match start_price {
+            Some(price) => self.check_manual_price(&duel.asset, price, now).await,
+            None => self.fresh_price_at(&duel.asset, now).await
+                .expect("No fresh oracle price for asset")
+                .price,
+        };
+
+        assert!(
+            duel.status == DuelStatus::WaitingForPredictions,
+            "Duel not in correct state"
+        );
+        assert!(duel.both_predicted(), "Both players must predict first");
+
+        if let Some(versus_asset) = duel.versus_asset.clone() {
+            self.check_asset_not_halted(&versus_asset).await;
+            let versus_price = self.fresh_price_at(&versus_asset, now).await
+                .expect("No fresh oracle price for versus asset")
+                .price;
+            duel.versus_start_price = Some(versus_price);
+            log::info!("Duel {:?} versus {} started at price {}", duel_id, versus_asset, versus_price);
+        }
+
+        // Lock in start price and begin timer
+        if let SettlementMode::Twap { window_micros } = duel.settlement {
+            let from = Timestamp::from(now.micros().saturating_sub(window_micros));
+            let start_twap = self.state.twap(&duel.asset, from, now).await
+                .expect("No oracle prices for TWAP window");
+            duel.start_twap = Some(start_twap);
+            log::info!("Duel {:?} start TWAP {}", duel_id, start_twap);
+            if let Some(versus_asset) = &duel.versus_asset {
+                let versus_start_twap = self.state.twap(versus_asset, from, now).await
+                    .expect("No oracle prices of versus asset for TWAP window");
+                duel.versus_start_twap = Some(versus_start_twap);
+            }
+        }
+        duel.start_price = Some(start_price);
+        duel.started_at = Some(now);
+        duel.status = DuelStatus::Active;
+
+        self.state.update_duel(duel).await;
+
+        // Side betting closes once the duel starts
+        if let Some(mut pool) = self.state.get_side_pool(duel_id).await {
+            pool.status = SidePoolStatus::Closed;
+            self.state.update_side_pool(pool).await;
+        }
+
+        log::info!("Duel {:?} started at price {}", duel_id, start_price);
+    }
+
+    async fn resolve_duel(&mut self, duel_id: DuelId, end_price: Option<Price>) {
+        let mut duel = self.state.get_duel(duel_id).await
+            .expect("Duel not found");
+
+        assert!(
+            duel.status == DuelStatus::Active,
+            "Duel not active"
+        );
+        let ends_at = duel.ends_at().expect("Duel has not started");
+        assert!(self.now() >= ends_at, "Duel still running");
+        self.check_asset_not_halted(&duel.asset).await;
+
+        // Unrevealed guesses forfeit only once the reveal window has passed
+        assert!(
+            duel.guesses_revealed()
+                || self.now().micros() > ends_at.micros().saturating_add(REVEAL_WINDOW_MICROS),
+            "Waiting for guesses to be revealed"
+        );
+
+        assert!(
+            end_price.is_none() || duel.mode != DuelMode::Relative,
+            "Relative duels lock prices from oracle state"
+        );
+        // A duel whose end quotes are missing or stale can never settle
+        // fairly, so every stake is refunded instead
+        if let Some(versus_asset) = duel.versus_asset.clone() {
+            self.check_asset_not_halted(&versus_asset).await;
+            let Some(versus_quote) = self.fresh_price_at(&versus_asset, ends_at).await else {
+                self.cancel_duel(duel_id, "No fresh oracle price for versus asset at duel end".to_string()).await;
+                return;
+            };
+            duel.versus_end_price = Some(versus_quote.price);
+        }
+
+        // Quotes are appended in block order, so the quote in effect at the
+        // end of the window is final once the window has passed
+        let end_price = match end_price {
+            Some(price) => self.check_manual_price(&duel.asset, price, ends_at).await,
+            None => match self.fresh_price_at(&duel.asset, ends_at).await {
+                Some(quote) => quote.price,
+                None => {
+                    self.cancel_duel(duel_id, "No fresh oracle price at duel end".to_string()).await;
+                    return;
+                }
+            },
+        };
+
+        // Set end price and determine winner
+        if let SettlementMode::Twap { window_micros } = duel.settlement {
+            let from = Timestamp::from(ends_at.micros().saturating_sub(window_micros));
+            let Some(end_twap) = self.state.twap(&duel.asset, from, ends_at).await else {
+                self.cancel_duel(duel_id, "No oracle prices for TWAP window".to_string()).await;
+                return;
+            };
+            duel.end_twap = Some(end_twap);
+            log::info!("Duel {:?} end TWAP {}", duel_id, end_twap);
+            if let Some(versus_asset) = duel.versus_asset.clone() {
+                let Some(versus_end_twap) = self.state.twap(&versus_asset, from, ends_at).await else {
+                    self.cancel_duel(duel_id, "No oracle prices of versus asset for TWAP window".to_string()).await;
+                    return;
+                };
+                duel.versus_end_twap = Some(versus_end_twap);
+            }
+        }
+        duel.end_price = Some(end_price);
+        self.record_pair_predictions(&duel).await;
+        // Only range and closest-price duels can push, and those are never part of a series or tournament
+        let winner = if duel.mode == DuelMode::Relative {
+            duel.determine_relative_winner()
+        } else {
+            duel.determine_winner()
+        };
+        let Some(winner) = winner else {
+            self.push_duel(duel).await;
+            return;
+        };
+        let winning_team = duel.team_of(&winner).expect("Winner is not a participant");
+
+        self.assess_fees(&mut duel.participants).await;
+        let total_pot = duel.total_pot();
+        let fee = duel.total_fee();
+
+        // Losers get back the share of their stake the winners do not take
+        let winner_share_bps = duel.winner_share_bps();
+        let losses: Vec<_> = duel.team_stakes(winning_team.opposite())
+            .into_iter()
+            .map(|(player, stake)| (player, stake, platform_fee(stake, winner_share_bps)))
+            .collect();
+        let refunded = losses.iter()
+            .fold(Amount::ZERO, |total, (_, stake, lost)| total.saturating_add(stake.saturating_sub(*lost)));
+
+        // The winning team splits the rest of the pot in proportion to stake
+        let (payouts, dust) = pro_rata_split(
+            &duel.team_stakes(winning_team),
+            total_pot.saturating_sub(fee).saturating_sub(refunded),
+        );
+        let fee = fee.saturating_add(dust);
+        let fee = self.pay_fee_shares(duel_id, &duel.participants, fee).await;
+
+        // Credit winners and update stats; only staked 1v1 duels build the jackpot streak
+        let jackpot_eligible = duel.participants.len() == 2 && total_pot > Amount::ZERO;
+        for (player, payout) in &payouts {
+            self.state.credit(player, *payout).await;
+            if *player == HOUSE_ACCOUNT {
+                continue;
+            }
+            let mut stats = self.state.get_stats(player).await;
+            stats.record_win(*payout);
+            stats.record_duel_result(jackpot_eligible);
+            let duel_win_streak = stats.duel_win_streak;
+            self.state.update_stats(player, stats).await;
+            if jackpot_eligible {
+                self.check_jackpot(duel_id, player, duel_win_streak).await;
+            }
+        }
+        for (player, stake, lost) in losses {
+            self.state.credit(&player, stake.saturating_sub(lost)).await;
+            if player == HOUSE_ACCOUNT {
+                continue;
+            }
+            let mut stats = self.state.get_stats(&player).await;
+            stats.record_loss();
+            stats.record_duel_result(false);
+            self.state.update_stats(&player, stats).await;
+            self.record_gaming_activity(&player, LimitKind::Loss, lost).await;
+        }
+
+        // Record volume; house duels pay their fee to the liquidity providers
+        if duel.is_house_duel() {
+            self.state.credit(&HOUSE_ACCOUNT, fee).await;
+            self.state.record_volume(total_pot, Amount::ZERO).await;
+        } else {
+            self.state.record_volume(total_pot, fee).await;
+        }
+
+        // Complete duel
+        self.state.complete_duel(duel.clone()).await;
+        self.settle_side_pool(duel_id, Some(winning_team)).await;
+
+        log::info!(
+            "Duel {:?} resolved: winner={:?} (team {:?}), payouts={:?}, fee={:?}",
+            duel_id, winner, winning_team, payouts, fee
+        );
+
+        // Series and tournament duels carry no stake of their own
+        if let Some(series_id) = duel.series_id {
+            self.advance_series(series_id, duel_id, winner).await;
+        }
+        if let Some(tournament_id) = duel.tournament_id {
+            self.advance_tournament(tournament_id, duel_id, winner).await;
+        }
+    }
+
+    /// Charge each participant the fee rate of their volume tier.
+    async fn assess_fees(&mut self, participants: &mut [Participant]) {
+        let fee_bps = *self.state.fee_bps.get();
+        let fee_tiers = self.state.fee_tiers.get().clone();
+        for participant in participants.iter_mut() {
+            // The house keeps no stats and always pays the base rate
+            let rate = if participant.player == HOUSE_ACCOUNT {
+                fee_bps
+            } else {
+                let stats = self.state.get_stats(&participant.player).await;
+                tier_fee_bps(&fee_tiers, stats.total_wagered, fee_bps)
+            };
+            participant.fee = platform_fee(participant.stake, rate);
+        }
+    }
+
+    /// Pay the referral, jackpot and rakeback shares of the fees charged to
+    /// the participants, returning what is left of `fee` for the platform.
+    async fn pay_fee_shares(&mut self, duel_id: DuelId, participants: &[Participant], fee: Amount) -> Amount {
+        // Referral, jackpot and rakeback shares are all taken from the fee
+        // charged, and together never exceed it
+
+        // Referrers earn a share of the fee on each referred participant's stake
+        let referral_paid = self.pay_referrals(duel_id, participants).await;
+
+        // Feed a share of the fee into the jackpot
+        let charged = participants.iter()
+            .fold(Amount::ZERO, |total, p| total.saturating_add(p.fee));
+        let jackpot_cut = platform_fee(charged, *self.state.jackpot_fee_share_bps.get());
+        let jackpot = self.state.jackpot_pool.get().saturating_add(jackpot_cut);
+        self.state.jackpot_pool.set(jackpot);
+
+        // Set aside each player's rakeback until they claim it
+        let rakeback_accrued = self.record_fees_paid(participants).await;
+
+        fee
+            .saturating_sub(referral_paid)
+            .saturating_sub(jackpot_cut)
+            .saturating_sub(rakeback_accrued)
+    }
+
+    /// Track the fee each player paid in a settled duel for stats and accrue
+    /// their rakeback on it, returning the total rakeback accrued.
+    async fn record_fees_paid(&mut self, participants: &[Participant]) -> Amount {
+        let rakeback_bps = *self.state.rakeback_bps.get();
+        let mut accrued = Amount::ZERO;
+
+        for participant in participants {
+            if participant.player == HOUSE_ACCOUNT || participant.fee == Amount::ZERO {
+                continue;
+            }
+            let mut stats = self.state.get_stats(&participant.player).await;
+            stats.record_fee(participant.fee);
+            self.state.update_stats(&participant.player, stats).await;
+
+            let mut account = self.state.get_rakeback_account(&participant.player).await;
+            let rakeback = platform_fee(participant.fee, rakeback_bps);
+            account.unclaimed_fees = account.unclaimed_fees.saturating_add(participant.fee);
+            account.accrued = account.accrued.saturating_add(rakeback);
+            self.state.update_rakeback_account(&participant.player, account).await;
+            accrued = accrued.saturating_add(rakeback);
+        }
+
+        accrued
+    }
+
+    /// Credit referrers their share of the fee charged on each referred
+    /// participant's stake, returning the total paid.
+    async fn pay_referrals(&mut self, duel_id: DuelId, participants: &[Participant]) -> Amount {
+        let share_bps = *self.state.referral_fee_share_bps.get();
+        let mut paid = Amount::ZERO;
+
+        for participant in participants {
+            let Some(referrer) = self.state.get_referrer(&participant.player).await else {
+                continue;
+            };
+            let amount = platform_fee(participant.fee, share_bps);
+            if amount == Amount::ZERO {
+                continue;
+            }
+            self.state.credit_referral(&referrer, amount).await;
+            paid = paid.saturating_add(amount);
+
+            log::info!(
+                "Referrer {:?} earned {:?} from {:?} in duel {:?}",
+                referrer, amount, participant.player, duel_id
+            );
+        }
+
+        paid
+    }
+
+    /// Count a duel for each pair of opponents, flagging pairs that meet too often.
+    async fn record_pair_duels(&mut self, duel_id: DuelId, pairs: &[(AccountOwner, AccountOwner)]) {
+        let now = self.now();
+        let window_micros = *self.state.pair_window_micros.get();
+        let threshold = *self.state.pair_flag_threshold.get();
+
+        for (a, b) in pairs {
+            let mut stats = self.state.get_pair_stats(a, b).await;
+            stats.record_duel(now, window_micros);
+            if stats.flagged_at.is_none() && stats.window_duels >= threshold {
+                stats.flagged_at = Some(now);
+                log::warn!(
+                    "Pair {:?} / {:?} flagged: {} duels within the window (duel {:?})",
+                    a, b, stats.window_duels, duel_id
+                );
+            }
+            self.state.update_pair_stats(a, b, stats).await;
+        }
+    }
+
+    /// Count settled duels and those where opposing teams called opposite
+    /// outcomes, flagging pairs that almost always take opposite sides.
+    async fn record_pair_predictions(&mut self, duel: &DuelInfo) {
+        let opposite = match (duel.p1_prediction, duel.p2_prediction) {
+            (Some(p1), Some(p2)) => p1.opposite() == Some(p2),
+            _ => false,
+        };
+        let now = self.now();
+        let min_settled = u64::from(*self.state.pair_flag_threshold.get());
+        let ratio_limit = *self.state.pair_opposite_ratio_bps.get();
+
+        for (a, b) in duel.opposing_pairs() {
+            let mut stats = self.state.get_pair_stats(&a, &b).await;
+            stats.settled += 1;
+            if opposite {
+                stats.opposite_predictions += 1;
+            }
+            if stats.flagged_at.is_none()
+                && stats.settled >= min_settled
+                && ratio_limit.is_some_and(|limit| stats.opposite_ratio_bps() >= u64::from(limit))
+            {
+                stats.flagged_at = Some(now);
+                log::warn!(
+                    "Pair {:?} / {:?} flagged: opposite predictions in {} of {} settled duels (duel {:?})",
+                    a, b, stats.opposite_predictions, stats.settled, duel.id
+                );
+            }
+            self.state.update_pair_stats(&a, &b, stats).await;
+        }
+    }
+
+    /// Pay out the jackpot if a duel winner's 1v1 streak has reached the trigger.
+    async fn check_jackpot(&mut self, duel_id: DuelId, player: &AccountOwner, win_streak: u64) {
+        let trigger = *self.state.jackpot_win_streak.get();
+        let amount = *self.state.jackpot_pool.get();
+        if *player == HOUSE_ACCOUNT
+            || win_streak == 0
+            || win_streak % trigger != 0
+            || amount == Amount::ZERO
+        {
+            return;
+        }
+
+        let now = self.now();
+        self.state.jackpot_pool.set(Amount::ZERO);
+        self.state.credit(player, amount).await;
+        self.state.jackpot_wins.push_back(JackpotWin {
+            player: *player,
+            duel_id,
+            win_streak,
+            amount,
+            won_at: now,
+        });
+
+        log::info!(
+            "Player {:?} won the {:?} jackpot on a {} duel win streak in duel {:?}",
+            player, amount, win_streak, duel_id
+        );
+    }
+
+    /// Return every stake of a resolved duel that ended without a winner.
+    async fn push_duel(&mut self, duel: DuelInfo) {
+        let duel_id = duel.id;
+
+        // A push breaks the jackpot streak
+        for participant in &duel.participants {
+            self.state.credit(&participant.player, participant.stake).await;
+            if participant.player == HOUSE_ACCOUNT {
+                continue;
+            }
+            let mut stats = self.state.get_stats(&participant.player).await;
+            stats.record_duel_result(false);
+            self.state.update_stats(&participant.player, stats).await;
+        }
+        self.state.complete_duel(duel).await;
+        self.settle_side_pool(duel_id, None).await;
+
+        log::info!("Duel {:?} pushed: no winner, stakes refunded", duel_id);
+    }
+
+    /// Cancel a duel on behalf of one of its participants or the admin.
+    async fn request_cancel_duel(&mut self, duel_id: DuelId, reason: String) {
+        let caller = self.signer();
+        let duel = self.state.get_duel(duel_id).await
+            .expect("Duel not found");
+
+        assert!(
+            duel.is_participant(&caller) || self.state.is_admin(&caller).await,
+            "Only participants or the admin can cancel a duel"
+        );
+        // Held duels are only released through the admin's clear or void
+        assert!(
+            duel.status != DuelStatus::PendingReview,
+            "Duel is pending review"
+        );
+
+        self.cancel_duel(duel_id, reason).await;
+    }
+
+    async fn cancel_duel(&mut self, duel_id: DuelId, reason: String) {
+        let mut duel = self.state.get_duel(duel_id).await
+            .expect("Duel not found");
+
+        assert!(
+            duel.status != DuelStatus::Resolved,
+            "Cannot cancel resolved duel"
+        );
+        assert!(
+            duel.status != DuelStatus::Cancelled,
+            "Duel already cancelled"
+        );
+
+        // Refund every participant
+        for participant in &duel.participants {
+            self.state.credit(&participant.player, participant.stake).await;
+        }
+
+        duel.status = DuelStatus::Cancelled;
+        self.state.complete_duel(duel.clone()).await;
+        self.settle_side_pool(duel_id, None).await;
+
+        log::info!("Duel {:?} cancelled: {}", duel_id, reason);
+
+        // A cancelled series duel ends the whole series
+        if let Some(series_id) = duel.series_id {
+            self.refund_series(series_id, &reason).await;
+        }
+        // A cancelled tournament duel is replayed so the bracket can continue
+        if let Some(tournament_id) = duel.tournament_id {
+            self.replay_tournament_match(tournament_id, duel_id).await;
+        }
+    }
+
+    async fn offer_rematch(&mut self, duel_id: DuelId, double_stake: bool) {
+        self.check_not_paused().await;
+        let player = self.signer();
+        self.check_player_eligible(&player).await;
+        let now = self.now();
+
+        let duel = self.state.get_recent_duel(duel_id).await
+            .expect("Duel not found in recent history");
+
+        assert!(
+            duel.status == DuelStatus::Resolved,
+            "Only resolved duels can be rematched"
+        );
+        assert!(
+            duel.series_id.is_none() && duel.tournament_id.is_none(),
+            "Series and tournament duels cannot be rematched"
+        );
+        assert!(!duel.is_team_duel(), "Team duels cannot be rematched");
+        assert!(!duel.is_house_duel(), "House duels cannot be rematched");
+        let opponent = duel.opponent_of(&player).expect("Not a participant");
+
+        let bet_amount = if double_stake {
+            duel.bet_amount.saturating_add(duel.bet_amount)
+        } else {
+            duel.bet_amount
+        };
+
+        // Validate asset and bet amount
+        self.check_asset_bet(&duel.asset, bet_amount).await;
+
+        let balance = self.state.get_balance(&player).await;
+        assert!(balance >= bet_amount, "Insufficient balance");
+
+        self.state.prune_rematch_offers(now).await;
+
+        let offer = RematchOffer {
+            duel_id,
+            offered_by: player,
+            opponent,
+            asset: duel.asset,
+            bet_amount,
+            duration_micros: duel.duration_micros,
+            mode: duel.mode,
+            versus_asset: duel.versus_asset,
+            offered_at: now,
+        };
+        self.state.set_rematch_offer(offer).await;
+
+        log::info!(
+            "Player {:?} offered {:?} a rematch of duel {:?} with bet {:?}",
+            player, opponent, duel_id, bet_amount
+        );
+    }
+
+    async fn accept_rematch(&mut self, duel_id: DuelId) {
+        self.check_not_paused().await;
+        let player = self.signer();
+        self.check_player_eligible(&player).await;
+        let now = self.now();
+
+        let offer = self.state.get_rematch_offer(duel_id).await
+            .expect("No rematch offer for this duel");
+
+        assert!(offer.opponent == player, "Rematch was not offered to this player");
+        assert!(!offer.is_expired(now), "Rematch offer expired");
+        self.check_player_eligible(&offer.offered_by).await;
+        self.check_asset_not_halted(&offer.asset).await;
+        if let Some(versus_asset) = &offer.versus_asset {
+            self.check_asset_not_halted(versus_asset).await;
+        }
+
+        // Neither player may be waiting in the queue with funds earmarked elsewhere
+        assert!(
+            !self.state.is_in_queue(&offer.offered_by).await && !self.state.is_in_queue(&player).await,
+            "Already in queue"
+        );
+
+        let offerer_balance = self.state.get_balance(&offer.offered_by).await;
+        let balance = self.state.get_balance(&player).await;
+        assert!(
+            offerer_balance >= offer.bet_amount && balance >= offer.bet_amount,
+            "Insufficient balance"
+        );
+        self.check_stake_limits(&offer.offered_by, offer.bet_amount).await;
+        self.check_stake_limits(&player, offer.bet_amount).await;
+        assert!(
+            !self.state.pair_at_limit(&offer.offered_by, &player, now).await,
+            "Match limit reached for this pair"
+        );
+
+        self.state.remove_rematch_offer(duel_id).await;
+
+        let new_duel_id = self.state.next_duel_id().await;
+        let mut duel = DuelInfo::new(
+            new_duel_id,
+            offer.offered_by,
+            player,
+            offer.asset,
+            offer.bet_amount,
+            offer.duration_micros,
+            now,
+        );
+        duel.previous_duel = Some(duel_id);
+        duel.mode = offer.mode;
+        duel.versus_asset = offer.versus_asset;
+        self.create_duel(duel).await;
+
+        log::info!("Rematch of duel {:?} accepted as duel {:?}", duel_id, new_duel_id);
+    }
+
+    async fn place_side_bet(&mut self, duel_id: DuelId, team: Team, amount: Amount) {
+        self.check_not_paused().await;
+        let bettor = self.signer();
+        self.check_player_eligible(&bettor).await;
+
+        let duel = self.state.get_duel(duel_id).await
+            .expect("Duel not found");
+        assert!(
+            duel.status == DuelStatus::WaitingForPredictions,
+            "Side betting is closed for this duel"
+        );
+        assert!(duel.team_of(&bettor).is_none(), "Participants cannot side bet on their own duel");
+
+        // Validate asset and stake
+        self.check_asset_bet(&duel.asset, amount).await;
+
+        let mut pool = self.state.get_side_pool(duel_id).await
+            .unwrap_or_else(|| SidePool::new(duel_id));
+        assert!(pool.team_of(&bettor).is_none(), "Already backed a team in this duel");
+        self.check_stake_limits(&bettor, amount).await;
+
+        self.state.debit(&bettor, amount).await
+            .expect("Insufficient balance");
+
+        let mut stats = self.state.get_stats(&bettor).await;
+        stats.record_side_bet(amount);
+        self.state.update_stats(&bettor, stats).await;
+        self.record_gaming_activity(&bettor, LimitKind::Wager, amount).await;
+
+        pool.bets.push(SideBet { bettor, team, amount });
+
+        log::info!(
+            "Spectator {:?} backed team {:?} with {:?} in duel {:?}",
+            bettor, team, amount, duel_id
+        );
+
+        self.state.update_side_pool(pool).await;
+    }
+
+    /// Pay out a duel's side pool to backers of the winning team, or refund
+    /// every bet if the duel had no winner or one side was never backed.
+    async fn settle_side_pool(&mut self, duel_id: DuelId, winning_team: Option<Team>) {
+        let Some(mut pool) = self.state.get_side_pool(duel_id).await else {
+            return;
+        };
+        pool.winning_team = winning_team;
+
+        let (winning, losing): (Vec<_>, Vec<_>) = pool.bets.iter()
+            .partition(|b| Some(b.team) == winning_team);
+
+        if winning.is_empty() || losing.is_empty() {
+            // No winner or no counterparty: refund every bet
+            for bet in &pool.bets {
+                self.state.credit(&bet.bettor, bet.amount).await;
+            }
+            pool.status = SidePoolStatus::Refunded;
+            log::info!("Side pool of duel {:?} refunded", duel_id);
+        } else {
+            let fee_bps = *self.state.fee_bps.get();
+            let losing_total = losing.iter()
+                .fold(Amount::ZERO, |total, b| total.saturating_add(b.amount));
+            let winning: Vec<_> = winning.iter().map(|b| (b.bettor, b.amount)).collect();
+            let losing: Vec<_> = losing.iter().map(|b| (b.bettor, b.amount)).collect();
+            let (payouts, fee) = parimutuel_payouts(&winning, losing_total, fee_bps);
+
+            for (bettor, payout) in payouts {
+                self.state.credit(&bettor, payout).await;
+                let mut stats = self.state.get_stats(&bettor).await;
+                stats.record_side_bet_win(payout);
+                self.state.update_stats(&bettor, stats).await;
+            }
+            for (bettor, amount) in losing {
+                let mut stats = self.state.get_stats(&bettor).await;
+                stats.record_side_bet_loss();
+                self.state.update_stats(&bettor, stats).await;
+                self.record_gaming_activity(&bettor, LimitKind::Loss, amount).await;
+            }
+
+            self.state.record_volume(pool.total_staked(), fee).await;
+            pool.status = SidePoolStatus::Settled;
+
+            log::info!(
+                "Side pool of duel {:?} settled: team={:?}, pool={:?}, fee={:?}",
+                duel_id, winning_team, pool.total_staked(), fee
+            );
+        }
+
+        self.state.complete_side_pool(pool).await;
+    }
+
+    // --- SERIES METHODS ---
+
+    async fn challenge_series(
+        &mut self,
+        opponent: AccountOwner,
+        asset: Asset,
+        stake: Amount,
+        best_of: u32,
+        duration_micros: u64,
+    ) {
+        self.check_not_paused().await;
+        let player = self.signer();
+        self.check_player_eligible(&player).await;
+        let now = self.now();
+
+        assert!(opponent != player, "Cannot challenge yourself");
+        assert!(
+            best_of % 2 == 1 && best_of <= MAX_SERIES_LENGTH,
+            "Series length must be odd and at most {}",
+            MAX_SERIES_LENGTH
+        );
+
+        // Validate asset and stake
+        self.check_asset_bet(&asset, stake).await;
+
+        assert!(
+            self.state.is_allowed_duration(duration_micros),
+            "Unsupported duel duration: {} micros",
+            duration_micros
+        );
+
+        let balance = self.state.get_balance(&player).await;
+        assert!(balance >= stake, "Insufficient balance");
+
+        let series_id = self.state.next_series_id().await;
+        log::info!(
+            "Player {:?} challenged {:?} to best-of-{} series {:?} on {} with stake {:?}",
+            player, opponent, best_of, series_id, asset, stake
+        );
+
+        let series = SeriesInfo::new(series_id, player, opponent, asset, stake, best_of, duration_micros, now);
+        self.state.update_series(series).await;
+    }
+
+    async fn accept_series(&mut self, series_id: SeriesId) {
+        self.check_not_paused().await;
+        let player = self.signer();
+        self.check_player_eligible(&player).await;
+        let now = self.now();
+
+        let mut series = self.state.get_series(series_id).await
+            .expect("Series not found");
+
+        assert!(series.status == SeriesStatus::Pending, "Series is not pending");
+        assert!(series.player2 == player, "Series challenge was not sent to this player");
+        self.check_player_eligible(&series.player1).await;
+        self.check_asset_not_halted(&series.asset).await;
+        self.check_stake_limits(&series.player1, series.stake).await;
+        self.check_stake_limits(&player, series.stake).await;
+        assert!(
+            !self.state.pair_at_limit(&series.player1, &player, now).await,
+            "Match limit reached for this pair"
+        );
+
+        // Escrow both stakes for the whole series
+        self.state.debit(&series.player1, series.stake).await
+            .expect("Challenger has insufficient balance");
+        self.state.debit(&player, series.stake).await
+            .expect("Insufficient balance");
+
+        let mut stats1 = self.state.get_stats(&series.player1).await;
+        let mut stats2 = self.state.get_stats(&series.player2).await;
+        stats1.record_bet(series.stake);
+        stats2.record_bet(series.stake);
+        self.state.update_stats(&series.player1, stats1).await;
+        self.state.update_stats(&series.player2, stats2).await;
+        self.record_gaming_activity(&series.player1, LimitKind::Wager, series.stake).await;
+        self.record_gaming_activity(&series.player2, LimitKind::Wager, series.stake).await;
+
+        series.status = SeriesStatus::InProgress;
+
+        // Start the first duel of the series
+        let duel_id = self.state.next_duel_id().await;
+        let mut duel = DuelInfo::new(
+            duel_id,
+            series.player1,
+            series.player2,
+            series.asset.clone(),
+            Amount::ZERO,
+            series.duration_micros,
+            now,
+        );
+        duel.series_id = Some(series_id);
+        series.duel_ids.push(duel_id);
+        self.state.update_series(series).await;
+        self.create_duel(duel).await;
+
+        log::info!("Series {:?} accepted, first duel {:?}", series_id, duel_id);
+    }
+
+    async fn cancel_series(&mut self, series_id: SeriesId) {
+        let player = self.signer();
+
+        let mut series = self.state.get_series(series_id).await
+            .expect("Series not found");
+
+        assert!(series.status == SeriesStatus::Pending, "Only pending series can be cancelled");
+        assert!(series.is_participant(&player), "Not a participant");
+
+        series.status = SeriesStatus::Cancelled;
+        self.state.update_series(series).await;
+
+        log::info!("Series {:?} cancelled by {:?}", series_id, player);
+    }
+
+    /// Record a resolved series duel and either settle the series or start the next duel.
+    async fn advance_series(&mut self, series_id: SeriesId, duel_id: DuelId, winner: AccountOwner) {
+        let mut series = self.state.get_series(series_id).await
+            .expect("Series not found");
+
+        if let Some(series_winner) = series.record_duel_winner(&winner) {
+            // Both escrowed stakes are charged like a single duel's
+            let mut participants = vec![
+                Participant { player: series.player1, team: Team::One, stake: series.stake, fee: Amount::ZERO },
+                Participant { player: series.player2, team: Team::Two, stake: series.stake, fee: Amount::ZERO },
+            ];
+            self.assess_fees(&mut participants).await;
+            let total_pot = series.total_pot();
+            let fee = participants.iter()
+                .fold(Amount::ZERO, |total, p| total.saturating_add(p.fee));
+            let payout = total_pot.saturating_sub(fee);
+
+            self.state.credit(&series_winner, payout).await;
+
+            // Duel wins and losses are already recorded per duel
+            let mut stats = self.state.get_stats(&series_winner).await;
+            stats.total_won = stats.total_won.saturating_add(payout);
+            self.state.update_stats(&series_winner, stats).await;
+            let series_loser = if series_winner == series.player1 { series.player2 } else { series.player1 };
+            self.record_gaming_activity(&series_loser, LimitKind::Loss, series.stake).await;
+
+            let fee = self.pay_fee_shares(duel_id, &participants, fee).await;
+            self.state.record_volume(total_pot, fee).await;
+
+            series.status = SeriesStatus::Completed;
+            self.state.update_series(series).await;
+
+            log::info!(
+                "Series {:?} completed: winner={:?}, payout={:?}, fee={:?}",
+                series_id, series_winner, payout, fee
+            );
+        } else {
+            let now = self.now();
+            let next_duel_id = self.state.next_duel_id().await;
+            let mut duel = DuelInfo::new(
+                next_duel_id,
+                series.player1,
+                series.player2,
+                series.asset.clone(),
+                Amount::ZERO,
+                series.duration_micros,
+                now,
+            );
+            duel.series_id = Some(series_id);
+            duel.previous_duel = Some(duel_id);
+            series.duel_ids.push(next_duel_id);
+            self.state.update_series(series).await;
+            self.create_duel(duel).await;
+
+            log::info!("Series {:?} continues with duel {:?}", series_id, next_duel_id);
+        }
+    }
+
+    /// Cancel an in-progress series and refund both escrowed stakes.
+    async fn refund_series(&mut self, series_id: SeriesId, reason: &str) {
+        let mut series = self.state.get_series(series_id).await
+            .expect("Series not found");
+
+        if series.status != SeriesStatus::InProgress {
+            return;
+        }
+
+        self.state.credit(&series.player1, series.stake).await;
+        self.state.credit(&series.player2, series.stake).await;
+
+        series.status = SeriesStatus::Cancelled;
+        self.state.update_series(series).await;
+
+        log::info!("Series {:?} cancelled: {}", series_id, reason);
+    }
+
+    // --- TOURNAMENT METHODS ---
+
+    async fn create_tournament(
+        &mut self,
+        asset: Asset,
+        entry_fee: Amount,
+        duration_micros: u64,
+        size: u32,
+        prize_split: Vec<u16>,
+    ) {
+        self.check_admin().await;
+        let now = self.now();
+
+        self.check_asset_enabled(&asset).await;
+        assert!(
+            self.state.is_allowed_duration(duration_micros),
+            "Unsupported duel duration: {} micros",
+            duration_micros
+        );
+        assert!(
+            size.is_power_of_two() && (2..=MAX_TOURNAMENT_SIZE).contains(&size),
+            "Tournament size must be a power of two between 2 and {}",
+            MAX_TOURNAMENT_SIZE
+        );
+
+        let tournament_id = self.state.next_tournament_id().await;
+        let tournament = TournamentInfo::new(
+            tournament_id,
+            asset,
+            entry_fee,
+            duration_micros,
+            size,
+            prize_split,
+            now,
+        );
+
+        assert!(
+            !tournament.prize_split.is_empty()
+                && tournament.prize_split.len() <= tournament.placement_tiers(),
+            "Prize split must cover between 1 and {} placement tiers",
+            tournament.placement_tiers()
+        );
+        assert!(
+            tournament.prize_split.iter().map(|bps| u32::from(*bps)).sum::<u32>() == 10000,
+            "Prize split must add up to 10000 basis points"
+        );
+
+        log::info!(
+            "Tournament {:?} created: {}, entry fee {:?}, {} players",
+            tournament.id, tournament.asset, tournament.entry_fee, tournament.size
+        );
+
+        self.state.update_tournament(tournament).await;
+    }
+
+    async fn cancel_tournament(&mut self, tournament_id: TournamentId) {
+        self.check_admin().await;
+
+        let mut tournament = self.state.get_tournament(tournament_id).await
+            .expect("Tournament not found");
+
+        assert!(
+            tournament.status == TournamentStatus::Registration,
+            "Only tournaments still in registration can be cancelled"
+        );
+
+        // Refund entry fees
+        for player in &tournament.players {
+            self.state.credit(player, tournament.entry_fee).await;
+        }
+
+        tournament.prize_pool = Amount::ZERO;
+        tournament.status = TournamentStatus::Cancelled;
+        self.state.update_tournament(tournament).await;
+
+        log::info!("Tournament {:?} cancelled", tournament_id);
+    }
+
+    async fn register_tournament(&mut self, tournament_id: TournamentId) {
+        self.check_not_paused().await;
+        let player = self.signer();
+        self.check_player_eligible(&player).await;
+
+        let mut tournament = self.state.get_tournament(tournament_id).await
+            .expect("Tournament not found");
+
+        assert!(
+            tournament.status == TournamentStatus::Registration,
+            "Tournament is not open for registration"
+        );
+        assert!(!tournament.is_registered(&player), "Already registered");
+        self.check_asset_not_halted(&tournament.asset).await;
+        self.check_stake_limits(&player, tournament.entry_fee).await;
+
+        self.state.debit(&player, tournament.entry_fee).await
+            .expect("Insufficient balance");
+
+        let mut stats = self.state.get_stats(&player).await;
+        stats.record_bet(tournament.entry_fee);
+        self.state.update_stats(&player, stats).await;
+        self.record_gaming_activity(&player, LimitKind::Wager, tournament.entry_fee).await;
+
+        tournament.players.push(player);
+        tournament.prize_pool = tournament.prize_pool.saturating_add(tournament.entry_fee);
+
+        log::info!("Player {:?} registered for tournament {:?}", player, tournament_id);
+
+        if tournament.is_full() {
+            // Seed the bracket and start the first round
+            tournament.status = TournamentStatus::InProgress;
+            let pairings = tournament.first_round_pairings();
+            self.start_tournament_round(tournament, pairings).await;
+        } else {
+            self.state.update_tournament(tournament).await;
+        }
+    }
+
+    /// Create bracket matches and duels for the tournament's current round.
+    ///
+    /// Blocked or self-excluded players forfeit their match, and a round
+    /// decided entirely by forfeits moves straight on to the next one.
+    async fn start_tournament_round(
+        &mut self,
+        mut tournament: TournamentInfo,
+        mut pairings: Vec<(AccountOwner, AccountOwner)>,
+    ) {
+        let now = self.now();
+        let mut duels = Vec::new();
+
+        loop {
+            for (slot, (player1, player2)) in pairings.into_iter().enumerate() {
+                let eligible1 = self.is_player_eligible(&player1).await;
+                let eligible2 = self.is_player_eligible(&player2).await;
+                if !(eligible1 && eligible2) {
+                    // If neither may play, player1 advances
+                    let winner = if eligible1 || !eligible2 { player1 } else { player2 };
+                    tournament.bracket.push(BracketMatch {
+                        round: tournament.current_round,
+                        slot: slot as u32,
+                        player1,
+                        player2,
+                        duel_id: None,
+                        winner: Some(winner),
+                    });
+                    log::info!(
+                        "Tournament {:?} round {} slot {}: {:?} advances by forfeit",
+                        tournament.id, tournament.current_round, slot, winner
+                    );
+                    continue;
+                }
+
+                let duel_id = self.state.next_duel_id().await;
+                let mut duel = DuelInfo::new(
+                    duel_id,
+                    player1,
+                    player2,
+                    tournament.asset.clone(),
+                    Amount::ZERO,
+                    tournament.duration_micros,
+                    now,
+                );
+                duel.tournament_id = Some(tournament.id);
+
+                tournament.bracket.push(BracketMatch {
+                    round: tournament.current_round,
+                    slot: slot as u32,
+                    player1,
+                    player2,
+                    duel_id: Some(duel_id),
+                    winner: None,
+                });
+                duels.push(duel);
+            }
+
+            log::info!(
+                "Tournament {:?} round {} started with {} matches",
+                tournament.id, tournament.current_round, duels.len()
+            );
+
+            if !duels.is_empty() || tournament.current_round + 1 >= tournament.rounds() {
+                break;
+            }
+            pairings = tournament.next_round_pairings();
+            tournament.current_round += 1;
+        }
+
+        if duels.is_empty() {
+            // The final was decided by forfeit
+            let champion = tournament.round_matches(tournament.current_round)
+                .next()
+                .and_then(|m| m.winner);
+            tournament.champion = champion;
+            self.distribute_tournament_prizes(tournament).await;
+            return;
+        }
+
+        self.state.update_tournament(tournament).await;
+        for duel in duels {
+            self.create_duel(duel).await;
+        }
+    }
+
+    /// Record a resolved tournament duel and advance the bracket.
+    async fn advance_tournament(
+        &mut self,
+        tournament_id: TournamentId,
+        duel_id: DuelId,
+        winner: AccountOwner,
+    ) {
+        let mut tournament = self.state.get_tournament(tournament_id).await
+            .expect("Tournament not found");
+
+        let bracket_match = tournament.match_for_duel_mut(duel_id)
+            .expect("Duel is not part of the bracket");
+        bracket_match.winner = Some(winner);
+
+        if !tournament.current_round_complete() {
+            self.state.update_tournament(tournament).await;
+            return;
+        }
+
+        if tournament.current_round + 1 < tournament.rounds() {
+            let pairings = tournament.next_round_pairings();
+            tournament.current_round += 1;
+            self.start_tournament_round(tournament, pairings).await;
+        } else {
+            tournament.champion = Some(winner);
+            self.distribute_tournament_prizes(tournament).await;
+        }
+    }
+
+    /// Pay out the prize pool by placement tier and complete the tournament.
+    async fn distribute_tournament_prizes(&mut self, mut tournament: TournamentInfo) {
+        let fee_bps = *self.state.fee_bps.get();
+        let total_pool = tournament.prize_pool;
+        let distributable = total_pool.saturating_sub(platform_fee(total_pool, fee_bps));
+
+        let mut paid = Amount::ZERO;
+        let mut prizes = Vec::new();
+        for (tier, players) in tournament.placements().into_iter().enumerate() {
+            let Some(share_bps) = tournament.prize_split.get(tier) else {
+                break;
+            };
+            if players.is_empty() {
+                continue;
+            }
+            let tier_prize = u128::from(distributable) * u128::from(*share_bps) / 10000;
+            let prize = Amount::from_attos(tier_prize / players.len() as u128);
+
+            for player in players {
+                self.state.credit(&player, prize).await;
+                let mut stats = self.state.get_stats(&player).await;
+                stats.total_won = stats.total_won.saturating_add(prize);
+                self.state.update_stats(&player, stats).await;
+                paid = paid.saturating_add(prize);
+                prizes.push((player, prize));
+
+                log::info!(
+                    "Tournament {:?}: placement {} prize {:?} to {:?}",
+                    tournament.id, tier + 1, prize, player
+                );
+            }
+        }
+
+        // Entrants whose prize fell short of the entry fee lost the difference
+        for player in tournament.players.clone() {
+            let prize = prizes.iter()
+                .find(|(winner, _)| *winner == player)
+                .map_or(Amount::ZERO, |(_, prize)| *prize);
+            let loss = tournament.entry_fee.saturating_sub(prize);
+            self.record_gaming_activity(&player, LimitKind::Loss, loss).await;
+        }
+
+        // Platform keeps the fee plus any rounding dust
+        let fee = total_pool.saturating_sub(paid);
+        self.state.record_volume(total_pool, fee).await;
+
+        tournament.status = TournamentStatus::Completed;
+        log::info!(
+            "Tournament {:?} completed: champion={:?}, pool={:?}, fee={:?}",
+            tournament.id, tournament.champion, total_pool, fee
+        );
+        self.state.update_tournament(tournament).await;
+    }
+
+    /// Replace a cancelled tournament duel with a fresh one between the same players.
+    async fn replay_tournament_match(&mut self, tournament_id: TournamentId, duel_id: DuelId) {
+        let mut tournament = self.state.get_tournament(tournament_id).await
+            .expect("Tournament not found");
+        let now = self.now();
+        let new_duel_id = self.state.next_duel_id().await;
+        let asset = tournament.asset.clone();
+        let duration_micros = tournament.duration_micros;
+
+        let bracket_match = tournament.match_for_duel_mut(duel_id)
+            .expect("Duel is not part of the bracket");
+        let mut duel = DuelInfo::new(
+            new_duel_id,
+            bracket_match.player1,
+            bracket_match.player2,
+            asset,
+            Amount::ZERO,
+            duration_micros,
+            now,
+        );
+        bracket_match.duel_id = Some(new_duel_id);
+        duel.tournament_id = Some(tournament_id);
+        duel.previous_duel = Some(duel_id);
+
+        self.state.update_tournament(tournament).await;
+        self.create_duel(duel).await;
+
+        log::info!(
+            "Tournament {:?}: duel {:?} replayed as duel {:?}",
+            tournament_id, duel_id, new_duel_id
+        );
+    }
+
+    // --- POOL METHODS ---
+
+    async fn set_pool_interval(&mut self, interval_micros: u64) {
+        self.check_admin().await;
+        assert!(interval_micros > 0, "Pool interval must be positive");
+        self.state.pool_interval_micros.set(interval_micros);
+        log::info!("Pool round interval set to {} micros", interval_micros);
+    }
+
+    async fn join_pool(&mut self, asset: Asset, direction: Direction, amount: Amount) {
+        self.check_not_paused().await;
+        let player = self.signer();
+        self.check_player_eligible(&player).await;
+        let now = self.now();
+
+        // Validate asset and stake
+        self.check_asset_bet(&asset, amount).await;
+
+        // Join the open round, or open a new one aligned to the interval
+        let mut round = match self.state.current_pool_round(&asset).await {
+            Some(round) if round.accepts_stakes(now) => round,
+            _ => {
+                let interval = *self.state.pool_interval_micros.get();
+                let opens_at = Timestamp::from(now.micros() - now.micros() % interval);
+                let round_id = self.state.next_round_id().await;
+                let round = PoolRound::new(round_id, asset.clone(), opens_at, interval);
+                self.state.set_current_pool_round(&round).await;
+                log::info!("Pool round {:?} opened for {}", round_id, asset);
+                round
+            }
+        };
+
+        assert!(!round.has_entry(&player), "Already staked in this round");
+        self.check_stake_limits(&player, amount).await;
+
+        self.state.debit(&player, amount).await
+            .expect("Insufficient balance");
+
+        let mut stats = self.state.get_stats(&player).await;
+        stats.record_bet(amount);
+        self.state.update_stats(&player, stats).await;
+        self.record_gaming_activity(&player, LimitKind::Wager, amount).await;
+
+        round.entries.push(PoolEntry { player, direction, amount });
+
+        log::info!(
+            "Player {:?} staked {:?} on {:?} in pool round {:?}",
+            player, amount, direction, round.id
+        );
+
+        self.state.update_pool_round(round).await;
+    }
+
+    async fn lock_pool_round(&mut self, round_id: RoundId) {
+        let now = self.now();
+
+        let mut round = self.state.get_pool_round(round_id).await
+            .expect("Pool round not found");
+
+        assert!(round.status == PoolRoundStatus::Open, "Pool round is not open");
+        assert!(now >= round.locks_at, "Pool round is still accepting stakes");
+        self.check_asset_not_halted(&round.asset).await;
+
+        // The start price is the quote in effect when staking closed, not when
+        // the lock happens to be sent
+        let price = self.state.price_at(&round.asset, round.locks_at).await
+            .expect("No oracle price at pool round lock");
+
+        round.start_price = Some(price.price);
+        round.status = PoolRoundStatus::Locked;
+
+        log::info!("Pool round {:?} locked at price {}", round_id, price.price);
+
+        self.state.update_pool_round(round).await;
+    }
+
+    async fn resolve_pool_round(&mut self, round_id: RoundId) {
+        let now = self.now();
+
+        let mut round = self.state.get_pool_round(round_id).await
+            .expect("Pool round not found");
+
+        assert!(round.status == PoolRoundStatus::Locked, "Pool round is not locked");
+        assert!(now >= round.resolves_at, "Pool round still running");
+        self.check_asset_not_halted(&round.asset).await;
+
+        // Quotes are appended in block order, so the quote in effect at the
+        // end of the round is final once the round has passed
+        let price = self.state.price_at(&round.asset, round.resolves_at).await
+            .expect("No oracle price at pool round end");
+
+        let start = round.start_price.expect("Pool round has no start price");
+        let outcome = if price.price.cmp_normalized(&start).is_gt() {
+            Direction::Up
+        } else {
+            Direction::Down
+        };
+        round.end_price = Some(price.price);
+        round.outcome = Some(outcome);
+
+        let winning: Vec<_> = round.entries.iter()
+            .filter(|e| e.direction == outcome)
+            .map(|e| (e.player, e.amount))
+            .collect();
+        let losing: Vec<_> = round.entries.iter()
+            .filter(|e| e.direction != outcome)
+            .map(|e| (e.player, e.amount))
+            .collect();
+
+        if winning.is_empty() || losing.is_empty() {
+            // No counterparty: refund every stake
+            for entry in &round.entries {
+                self.state.credit(&entry.player, entry.amount).await;
+            }
+            round.status = PoolRoundStatus::Refunded;
+            log::info!("Pool round {:?} refunded: one side had no stakes", round_id);
+        } else {
+            let fee_bps = *self.state.fee_bps.get();
+            let losing_total = round.total_for(outcome.opposite());
+            let (payouts, fee) = parimutuel_payouts(&winning, losing_total, fee_bps);
+
+            for (player, payout) in payouts {
+                self.state.credit(&player, payout).await;
+                let mut stats = self.state.get_stats(&player).await;
+                stats.record_win(payout);
+                self.state.update_stats(&player, stats).await;
+            }
+            for (player, amount) in losing {
+                let mut stats = self.state.get_stats(&player).await;
+                stats.record_loss();
+                self.state.update_stats(&player, stats).await;
+                self.record_gaming_activity(&player, LimitKind::Loss, amount).await;
+            }
+
+            self.state.record_volume(round.total_staked(), fee).await;
+            round.status = PoolRoundStatus::Resolved;
+
+            log::info!(
+                "Pool round {:?} resolved: outcome={:?}, pool={:?}, fee={:?}",
+                round_id, outcome, round.total_staked(), fee
+            );
+        }
+
+        self.state.complete_pool_round(round).await;
+    }
+
+    // --- HOUSE METHODS ---
+
+    async fn set_house_config(&mut self, enabled: bool, match_after_micros: u64) {
+        self.check_admin().await;
+        self.state.house_enabled.set(enabled);
+        self.state.house_match_after_micros.set(match_after_micros);
+        log::info!("House enabled: {}, matching after {} micros", enabled, match_after_micros);
+    }
+
+    async fn set_house_exposure_limit(&mut self, asset: Asset, limit: Amount) {
+        self.check_admin().await;
+
+        let mut config = self.state.get_asset_config(&asset).await
+            .unwrap_or_else(|| panic!("Unsupported asset: {}", asset));
+        config.house_exposure_limit = limit;
+        self.state.set_asset_config(config).await;
+
+        log::info!("House exposure limit for {} set to {:?}", asset, limit);
+    }
+
+    async fn provide_liquidity(&mut self, amount: Amount) {
+        self.check_not_paused().await;
+        let provider = self.signer();
+
+        assert!(amount > Amount::ZERO, "Amount must be positive");
+
+        // Shares are priced against the pool before the deposit
+        let pool_value = self.state.house_pool_value().await;
+        let total_shares = *self.state.total_lp_shares.get();
+        let shares = lp_shares_for(amount, pool_value, total_shares);
+        assert!(shares > 0, "Amount too small for a share");
+
+        self.state.debit(&provider, amount).await
+            .expect("Insufficient balance");
+        self.state.credit(&HOUSE_ACCOUNT, amount).await;
+
+        let held = self.state.get_lp_shares(&provider).await;
+        self.state.set_lp_shares(&provider, held + shares).await;
+
+        log::info!("LP {:?} provided {:?} for {} shares", provider, amount, shares);
+    }
+
+    async fn withdraw_liquidity(&mut self, shares: u128) {
+        let provider = self.signer();
+
+        let held = self.state.get_lp_shares(&provider).await;
+        assert!(shares > 0 && shares <= held, "Insufficient LP shares");
+
+        let pool_value = self.state.house_pool_value().await;
+        let total_shares = *self.state.total_lp_shares.get();
+        let value = lp_share_value(shares, pool_value, total_shares);
+
+        self.state.debit(&HOUSE_ACCOUNT, value).await
+            .expect("House liquidity is locked in active duels");
+        self.state.credit(&provider, value).await;
+        self.state.set_lp_shares(&provider, held - shares).await;
+
+        log::info!("LP {:?} redeemed {} shares for {:?}", provider, shares, value);
+    }
+
+    async fn match_with_house(&mut self) {
+        self.check_not_paused().await;
+        assert!(*self.state.house_enabled.get(), "House is disabled");
+        let now = self.now();
+        let match_after = *self.state.house_match_after_micros.get();
+
+        let entries: Vec<_> = self.state.queue.elements().await.unwrap_or_default();
+        let mut matched = 0;
+        for entry in entries {
+            if !self.house_can_take(&entry, now, match_after).await {
+                continue;
+            }
+            self.state.remove_from_queue(&entry.player).await;
+
+            let duel_id = self.state.next_duel_id().await;
+            let mut duel = DuelInfo::new(
+                duel_id,
+                entry.player,
+                HOUSE_ACCOUNT,
+                entry.asset,
+                entry.bet_amount,
+                entry.duration_micros,
+                now,
+            );
+            duel.mode = entry.mode;
+            duel.versus_asset = entry.versus_asset;
+            self.create_duel(duel).await;
+            matched += 1;
+        }
+
+        log::info!("House matched {} queued players", matched);
+    }
+
+    /// Check if the house may take the other side of a queue entry right now.
+    async fn house_can_take(&self, entry: &QueueEntry, now: Timestamp, match_after: u64) -> bool {
+        // Only solo duels whose predictions have an opposite side
+        let waited = now.micros().saturating_sub(entry.joined_at.micros()) >= match_after;
+        let supported = entry.team_size == 1
+            && matches!(entry.mode, DuelMode::Direction | DuelMode::Relative);
+        if !waited || !supported {
+            return false;
+        }
+
+        let Some(config) = self.state.get_asset_config(&entry.asset).await else {
+            return false;
+        };
+        let mut assets = vec![&entry.asset];
+        assets.extend(entry.versus_asset.as_ref());
+        for asset in assets {
+            if self.state.get_asset_halt(asset).await.is_some() {
+                return false;
+            }
+        }
+
+        let exposure = self.state.get_house_exposure(&entry.asset).await;
+        config.enabled
+            && exposure.saturating_add(entry.bet_amount) <= config.house_exposure_limit
+            && self.state.get_balance(&HOUSE_ACCOUNT).await >= entry.bet_amount
+            && self.state.get_balance(&entry.player).await >= entry.bet_amount
+    }
+
+    // --- ORACLE METHODS ---
+
+    async fn update_price(&mut self, asset: Asset, price: Price) {
+        let config = self.state.get_asset_config(&asset).await
+            .unwrap_or_else(|| panic!("Unsupported asset: {}", asset));
+        let price = price.with_decimals(config.price_decimals);
+
+        let reporter = self.runtime.authenticated_signer();
+        assert!(
+            config.is_oracle_source(reporter.as_ref()),
+            "Not an oracle source for {}",
+            asset
+        );
+
+        let now = self.now();
+        let halted = self.state.get_asset_halt(&asset).await.is_some();
+        let previous = self.state.get_price(&asset).await.filter(|_| !halted);
+
+        // Quotes arriving while halted are recorded without further checks
+        if let Some(previous) = previous {
+            let deviation = price.deviation_bps(&previous.price);
+            if config.max_deviation_bps.is_some_and(|max| deviation > u128::from(max)) {
+                // The outlier is not recorded, only kept on the halt for review
+                let reason = format!("Price moved {} bps from {}", deviation, previous.price);
+                self.trip_circuit_breaker(asset, price, reason).await;
+                return;
+            }
+
+            let gap = now.micros().saturating_sub(previous.timestamp.micros());
+            if config.max_staleness_micros.is_some_and(|max| gap > max) {
+                let reason = format!("No quote for {} micros", gap);
+                self.trip_circuit_breaker(asset.clone(), price, reason).await;
+            }
+        }
+
+        log::info!("Price updated: {} = {} {}", asset, price, config.quote_currency);
+
+        let price_data = PriceData {
+            asset,
+            price,
+            timestamp: now,
+        };
+
+        self.state.set_price(price_data).await;
+    }
+
+    /// Halt new matches and settlement on an asset and hold its running duels for review.
+    async fn trip_circuit_breaker(&mut self, asset: Asset, price: Price, reason: String) {
+        let halt = AssetHalt {
+            asset: asset.clone(),
+            reason,
+            price,
+            halted_at: self.now(),
+        };
+        log::warn!("Circuit breaker tripped for {} at {}: {}", asset, price, halt.reason);
+
+        let held = self.state.halt_asset(halt).await;
+        log::info!("Duels held for review on {}: {:?}", asset, held);
+    }
+
+    // --- MESSAGE HANDLERS ---
+
+    async fn handle_payout(&mut self, duel_id: DuelId, amount: Amount) {
+        let player = self.signer();
+        self.state.credit(&player, amount).await;
+        log::info!("Payout received for duel {:?}: {:?} to {:?}", duel_id, amount, player);
+    }
+
+    async fn handle_refund(&mut self, duel_id: DuelId, amount: Amount) {
+        let player = self.signer();
+        self.state.credit(&player, amount).await;
+        log::info!("Refund received for duel {:?}: {:?} to {:?}", duel_id, amount, player);
+    }
+
+    async fn handle_duel_completed(
+        &mut self,
+        duel_id: DuelId,
+        winner: AccountOwner,
+        loser: AccountOwner,
+        pot: Amount,
+    ) {
+        log::info!(
+            "Duel {:?} completed: winner={:?}, loser={:?}, pot={:?}",
+            duel_id, winner, loser, pot
         );
     }
 
@@ -622,3 +2795,1137 @@ impl SpeedBetContract {
         log::info!("Duel {:?} cancelled: {}", duel_id, reason);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linera_sdk::{linera_base_types::ChainId, util::BlockingWait};
+
+    fn admin() -> AccountOwner {
+        AccountOwner::Reserved(1)
+    }
+
+    fn player(n: u8) -> AccountOwner {
+        AccountOwner::Reserved(n)
+    }
+
+    fn tokens(n: u128) -> Amount {
+        Amount::from_tokens(n)
+    }
+
+    fn btc() -> Asset {
+        Asset::new("BTC")
+    }
+
+    /// Contract created by `admin()` with a 2% fee and bets of 1 to 100 tokens
+    fn create_contract() -> SpeedBetContract {
+        let runtime = ContractRuntime::new()
+            .with_application_parameters(())
+            .with_chain_id(ChainId(CryptoHash::test_hash("speedbet")))
+            .with_authenticated_signer(admin())
+            .with_system_time(Timestamp::from(0));
+        let mut contract = SpeedBetContract {
+            state: SpeedBetState::load(runtime.root_view_storage_context())
+                .blocking_wait()
+                .expect("Failed to read from mock key value store"),
+            runtime,
+        };
+        contract
+            .instantiate(InstantiationArgument {
+                fee_bps: 200,
+                min_bet: tokens(1),
+                max_bet: tokens(100),
+            })
+            .blocking_wait();
+        contract
+    }
+
+    /// Execute an operation signed by `signer` at `time` (in microseconds)
+    fn execute(contract: &mut SpeedBetContract, signer: AccountOwner, time: u64, operation: Operation) {
+        contract.runtime.set_authenticated_signer(Some(signer));
+        contract.runtime.set_system_time(Timestamp::from(time));
+        contract.execute_operation(operation).blocking_wait();
+    }
+
+    fn deposit(contract: &mut SpeedBetContract, player: AccountOwner, amount: Amount) {
+        execute(contract, player, 0, Operation::Deposit { amount });
+    }
+
+    fn post_price(contract: &mut SpeedBetContract, asset: &str, price: u128, time: u64) {
+        let operation = Operation::UpdatePrice { asset: Asset::new(asset), price: Price::new(price, 0) };
+        execute(contract, admin(), time, operation);
+    }
+
+    fn balance(contract: &SpeedBetContract, player: AccountOwner) -> Amount {
+        contract.state.get_balance(&player).blocking_wait()
+    }
+
+    fn stats(contract: &SpeedBetContract, player: AccountOwner) -> PlayerStats {
+        contract.state.get_stats(&player).blocking_wait()
+    }
+
+    fn join_queue(contract: &mut SpeedBetContract, player: AccountOwner, stake: Amount, time: u64) {
+        let operation = Operation::JoinQueue {
+            asset: btc(),
+            bet_amount: stake,
+            duration_micros: DUEL_DURATION_MICROS,
+            team_size: 1,
+            mode: DuelMode::Direction,
+            versus_asset: None,
+        };
+        execute(contract, player, time, operation);
+    }
+
+    /// Id of the duel created last
+    fn last_duel_id(contract: &SpeedBetContract) -> DuelId {
+        DuelId(*contract.state.next_duel_id.get() - 1)
+    }
+
+    /// Have both sides of a waiting direction duel call it, player1 up
+    fn predict(contract: &mut SpeedBetContract, duel_id: DuelId, time: u64) {
+        let duel = contract.state.get_duel(duel_id).blocking_wait().expect("Duel not found");
+        for (player, direction) in [(duel.player1, Direction::Up), (duel.player2, Direction::Down)] {
+            let operation = Operation::SubmitPrediction { duel_id, prediction: Prediction::Direction(direction) };
+            execute(contract, player, time, operation);
+        }
+    }
+
+    /// Start a predicted BTC duel at `time` and resolve it with the price
+    /// moving from 100 to `end_price`
+    fn settle(contract: &mut SpeedBetContract, duel_id: DuelId, end_price: u128, time: u64) {
+        post_price(contract, "BTC", 100, time);
+        execute(contract, admin(), time, Operation::StartDuel { duel_id, start_price: None });
+        let ends_at = time + DUEL_DURATION_MICROS;
+        post_price(contract, "BTC", end_price, ends_at);
+        execute(contract, admin(), ends_at, Operation::ResolveDuel { duel_id, end_price: None });
+    }
+
+    /// Match two players in a BTC direction duel that the first one wins
+    fn play_duel(contract: &mut SpeedBetContract, first: AccountOwner, second: AccountOwner, stake: Amount, time: u64) -> DuelId {
+        join_queue(contract, first, stake, time);
+        join_queue(contract, second, stake, time);
+        let duel_id = last_duel_id(contract);
+        predict(contract, duel_id, time);
+        settle(contract, duel_id, 110, time);
+        duel_id
+    }
+
+    /// Allow 30 second and 5 minute duels only and queue two players for a 5 minute one
+    fn match_five_minute_duel(contract: &mut SpeedBetContract) -> DuelId {
+        let durations = vec![30_000_000, 300_000_000];
+        execute(contract, admin(), 0, Operation::SetDuelDurations { durations });
+        for player in [player(10), player(11)] {
+            deposit(contract, player, tokens(50));
+            let operation = Operation::JoinQueue {
+                asset: btc(),
+                bet_amount: tokens(10),
+                duration_micros: 300_000_000,
+                team_size: 1,
+                mode: DuelMode::Direction,
+                versus_asset: None,
+            };
+            execute(contract, player, 0, operation);
+        }
+        let duel_id = last_duel_id(contract);
+        predict(contract, duel_id, 0);
+        post_price(contract, "BTC", 100, 0);
+        execute(contract, admin(), 0, Operation::StartDuel { duel_id, start_price: None });
+        duel_id
+    }
+
+    /// List SOL with bets of 2 to 5 tokens, quoted by `player(5)` only
+    fn list_sol(contract: &mut SpeedBetContract) {
+        let operation = Operation::ListAsset {
+            symbol: "SOL".to_string(),
+            display_decimals: 2,
+            quote_currency: "USD".to_string(),
+            price_decimals: 4,
+            min_bet: Some(tokens(2)),
+            max_bet: Some(tokens(5)),
+            oracle_sources: vec![player(5)],
+        };
+        execute(contract, admin(), 0, operation);
+    }
+
+    fn join_sol_queue(contract: &mut SpeedBetContract, player: AccountOwner, stake: Amount) {
+        let operation = Operation::JoinQueue {
+            asset: Asset::new("SOL"),
+            bet_amount: stake,
+            duration_micros: DUEL_DURATION_MICROS,
+            team_size: 1,
+            mode: DuelMode::Direction,
+            versus_asset: None,
+        };
+        execute(contract, player, 0, operation);
+    }
+
+    /// Start a BTC duel at 100 with a 10% deviation breaker, then trip the
+    /// breaker with a quote of 150
+    fn hold_duel_for_review(contract: &mut SpeedBetContract) -> DuelId {
+        let operation = Operation::SetCircuitBreaker {
+            asset: btc(),
+            max_deviation_bps: Some(1_000),
+            max_staleness_micros: None,
+        };
+        execute(contract, admin(), 0, operation);
+        deposit(contract, player(10), tokens(50));
+        deposit(contract, player(11), tokens(50));
+        join_queue(contract, player(10), tokens(10), 0);
+        join_queue(contract, player(11), tokens(10), 0);
+        let duel_id = last_duel_id(contract);
+        predict(contract, duel_id, 0);
+        post_price(contract, "BTC", 100, 0);
+        execute(contract, admin(), 0, Operation::StartDuel { duel_id, start_price: None });
+        post_price(contract, "BTC", 150, 10_000_000);
+        duel_id
+    }
+
+    /// Fund players 10 and 11 and match them in a 10 token BTC duel of `mode`
+    fn match_mode(contract: &mut SpeedBetContract, mode: DuelMode, versus_asset: Option<Asset>) -> DuelId {
+        for player in [player(10), player(11)] {
+            deposit(contract, player, tokens(50));
+            let operation = Operation::JoinQueue {
+                asset: btc(),
+                bet_amount: tokens(10),
+                duration_micros: DUEL_DURATION_MICROS,
+                team_size: 1,
+                mode,
+                versus_asset: versus_asset.clone(),
+            };
+            execute(contract, player, 0, operation);
+        }
+        last_duel_id(contract)
+    }
+
+    fn commit_guess(contract: &mut SpeedBetContract, duel_id: DuelId, player: AccountOwner, target: u128, salt: &str) {
+        let reveal = GuessReveal { duel_id, target: Price::new(target, 0), salt: salt.to_string() };
+        execute(contract, player, 0, Operation::CommitGuess { duel_id, commitment: reveal.commitment() });
+    }
+
+    fn reveal_guess(contract: &mut SpeedBetContract, duel_id: DuelId, player: AccountOwner, target: u128, salt: &str, time: u64) {
+        let operation = Operation::RevealGuess { duel_id, target: Price::new(target, 0), salt: salt.to_string() };
+        execute(contract, player, time, operation);
+    }
+
+    /// Match a closest-price duel where player 10 guesses 105 and player 11
+    /// guesses 120, and run it until the price ends at 108
+    fn run_closest_price_duel(contract: &mut SpeedBetContract) -> DuelId {
+        let duel_id = match_mode(contract, DuelMode::ClosestPrice, None);
+        commit_guess(contract, duel_id, player(10), 105, "salt-10");
+        commit_guess(contract, duel_id, player(11), 120, "salt-11");
+        post_price(contract, "BTC", 100, 0);
+        execute(contract, admin(), 0, Operation::StartDuel { duel_id, start_price: None });
+        post_price(contract, "BTC", 108, DUEL_DURATION_MICROS);
+        duel_id
+    }
+
+    /// Queue `player` for a 10 token 2v2 BTC direction duel
+    fn join_team_queue(contract: &mut SpeedBetContract, player: AccountOwner) {
+        let operation = Operation::JoinQueue {
+            asset: btc(),
+            bet_amount: tokens(10),
+            duration_micros: DUEL_DURATION_MICROS,
+            team_size: 2,
+            mode: DuelMode::Direction,
+            versus_asset: None,
+        };
+        execute(contract, player, 0, operation);
+    }
+
+    fn queued_players(contract: &SpeedBetContract) -> Vec<AccountOwner> {
+        let entries = contract.state.queue.elements().blocking_wait().unwrap_or_default();
+        entries.iter().map(|entry| entry.player).collect()
+    }
+
+    fn set_collusion_limits(contract: &mut SpeedBetContract, window_micros: u64, flag_threshold: u32, max_pair_matches: Option<u32>) {
+        let operation = Operation::SetCollusionLimits {
+            window_micros,
+            flag_threshold,
+            max_pair_matches,
+            opposite_ratio_bps: Some(PAIR_OPPOSITE_RATIO_BPS),
+        };
+        execute(contract, admin(), 0, operation);
+    }
+
+    fn set_daily_limit(contract: &mut SpeedBetContract, player: AccountOwner, kind: LimitKind, amount: Amount) {
+        let operation = Operation::SetGamingLimit { kind, period: LimitPeriod::Day, amount: Some(amount) };
+        execute(contract, player, 0, operation);
+    }
+
+    #[test]
+    fn accepted_rematch_starts_a_duel_with_the_offered_stake() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        let duel_id = play_duel(&mut contract, player(10), player(11), tokens(10), 0);
+
+        let offered_at = 2 * DUEL_DURATION_MICROS;
+        execute(&mut contract, player(11), offered_at, Operation::OfferRematch { duel_id, double_stake: true });
+        execute(&mut contract, player(10), offered_at + REMATCH_WINDOW_MICROS, Operation::AcceptRematch { duel_id });
+
+        let rematch = contract.state.get_duel(last_duel_id(&contract)).blocking_wait().expect("Rematch not created");
+        assert_eq!(rematch.previous_duel, Some(duel_id));
+        assert_eq!(rematch.player1, player(11));
+        assert_eq!(rematch.player2, player(10));
+        assert_eq!(rematch.bet_amount, tokens(20));
+        assert!(contract.state.get_rematch_offer(duel_id).blocking_wait().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Rematch offer expired")]
+    fn rematch_offer_expires_after_the_window() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        let duel_id = play_duel(&mut contract, player(10), player(11), tokens(10), 0);
+
+        let offered_at = 2 * DUEL_DURATION_MICROS;
+        execute(&mut contract, player(10), offered_at, Operation::OfferRematch { duel_id, double_stake: false });
+        execute(&mut contract, player(11), offered_at + REMATCH_WINDOW_MICROS + 1, Operation::AcceptRematch { duel_id });
+    }
+
+    #[test]
+    fn series_escrows_stakes_and_pays_the_winner_after_fees() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        let operation = Operation::ChallengeSeries {
+            opponent: player(11),
+            asset: btc(),
+            stake: tokens(10),
+            best_of: 3,
+            duration_micros: DUEL_DURATION_MICROS,
+        };
+        execute(&mut contract, player(10), 0, operation);
+        let series_id = SeriesId(1);
+        execute(&mut contract, player(11), 0, Operation::AcceptSeries { series_id });
+        assert_eq!(balance(&contract, player(10)), tokens(40));
+        assert_eq!(balance(&contract, player(11)), tokens(40));
+
+        // Player 10 takes the first two duels
+        for round in 0..2 {
+            let time = round * 2 * DUEL_DURATION_MICROS;
+            let duel_id = last_duel_id(&contract);
+            predict(&mut contract, duel_id, time);
+            settle(&mut contract, duel_id, 110, time);
+        }
+
+        let series = contract.state.get_series(series_id).blocking_wait().expect("Series not found");
+        assert_eq!(series.status, SeriesStatus::Completed);
+        assert_eq!(series.winner, Some(player(10)));
+        // Each stake pays the 2% fee
+        assert_eq!(balance(&contract, player(10)), tokens(40).saturating_add(Amount::from_millis(19_600)));
+        assert_eq!(balance(&contract, player(11)), tokens(40));
+        assert_eq!(stats(&contract, player(11)).fees_paid, Amount::from_millis(200));
+    }
+
+    #[test]
+    fn cancelling_a_series_duel_refunds_both_stakes() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        let operation = Operation::ChallengeSeries {
+            opponent: player(11),
+            asset: btc(),
+            stake: tokens(10),
+            best_of: 3,
+            duration_micros: DUEL_DURATION_MICROS,
+        };
+        execute(&mut contract, player(10), 0, operation);
+        execute(&mut contract, player(11), 0, Operation::AcceptSeries { series_id: SeriesId(1) });
+
+        let duel_id = last_duel_id(&contract);
+        execute(&mut contract, player(11), 0, Operation::CancelDuel { duel_id, reason: "test".to_string() });
+
+        let series = contract.state.get_series(SeriesId(1)).blocking_wait().expect("Series not found");
+        assert_eq!(series.status, SeriesStatus::Cancelled);
+        assert_eq!(balance(&contract, player(10)), tokens(50));
+        assert_eq!(balance(&contract, player(11)), tokens(50));
+    }
+
+    #[test]
+    fn duel_settles_after_its_chosen_duration() {
+        let mut contract = create_contract();
+        let duel_id = match_five_minute_duel(&mut contract);
+
+        post_price(&mut contract, "BTC", 110, 300_000_000);
+        execute(&mut contract, admin(), 300_000_000, Operation::ResolveDuel { duel_id, end_price: None });
+
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not resolved");
+        assert_eq!(duel.duration_micros, 300_000_000);
+        assert_eq!(duel.ends_at(), Some(Timestamp::from(300_000_000)));
+        assert_eq!(duel.winner, Some(player(10)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Duel still running")]
+    fn duel_cannot_settle_before_its_chosen_duration() {
+        let mut contract = create_contract();
+        let duel_id = match_five_minute_duel(&mut contract);
+
+        post_price(&mut contract, "BTC", 110, DUEL_DURATION_MICROS);
+        execute(&mut contract, admin(), DUEL_DURATION_MICROS, Operation::ResolveDuel { duel_id, end_price: None });
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsupported duel duration")]
+    fn queue_rejects_durations_that_are_not_offered() {
+        let mut contract = create_contract();
+        execute(&mut contract, admin(), 0, Operation::SetDuelDurations { durations: vec![30_000_000] });
+        deposit(&mut contract, player(10), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+    }
+
+    #[test]
+    fn listed_asset_takes_bets_and_quotes_from_its_oracles() {
+        let mut contract = create_contract();
+        list_sol(&mut contract);
+        deposit(&mut contract, player(10), tokens(50));
+        join_sol_queue(&mut contract, player(10), tokens(3));
+        assert_eq!(contract.state.queue.count(), 1);
+
+        let operation = Operation::UpdatePrice { asset: Asset::new("SOL"), price: "142.5".parse().unwrap() };
+        execute(&mut contract, player(5), 0, operation);
+        let quote = contract.state.get_price(&Asset::new("SOL")).blocking_wait().expect("No SOL quote");
+        assert_eq!(quote.price, Price::new(1_425_000, -4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Bet must be between")]
+    fn listed_asset_enforces_its_bet_limits() {
+        let mut contract = create_contract();
+        list_sol(&mut contract);
+        deposit(&mut contract, player(10), tokens(50));
+        join_sol_queue(&mut contract, player(10), tokens(6));
+    }
+
+    #[test]
+    #[should_panic(expected = "Asset is disabled")]
+    fn disabled_asset_takes_no_bets() {
+        let mut contract = create_contract();
+        list_sol(&mut contract);
+        execute(&mut contract, admin(), 0, Operation::SetAssetEnabled { asset: Asset::new("SOL"), enabled: false });
+        deposit(&mut contract, player(10), tokens(50));
+        join_sol_queue(&mut contract, player(10), tokens(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not an oracle source")]
+    fn only_listed_oracles_quote_an_asset() {
+        let mut contract = create_contract();
+        list_sol(&mut contract);
+        let operation = Operation::UpdatePrice { asset: Asset::new("SOL"), price: Price::new(142, 0) };
+        execute(&mut contract, player(6), 0, operation);
+    }
+
+    #[test]
+    fn price_history_keeps_the_latest_quotes_past_its_length() {
+        let mut contract = create_contract();
+        execute(&mut contract, admin(), 0, Operation::SetPriceHistoryLength { length: 2 });
+
+        // Quotes an hour apart are all outside the retention window of the next
+        let hour = 3_600_000_000;
+        for (i, price) in [100, 101, 102, 103].into_iter().enumerate() {
+            post_price(&mut contract, "BTC", price, i as u64 * hour);
+        }
+
+        let history = contract.state.get_price_history(&btc()).blocking_wait();
+        let prices: Vec<_> = history.iter().map(|quote| quote.price).collect();
+        assert_eq!(prices, vec![Price::new(102_000_000, -6), Price::new(103_000_000, -6)]);
+    }
+
+    #[test]
+    fn price_history_keeps_quotes_running_duels_may_need() {
+        let mut contract = create_contract();
+        execute(&mut contract, admin(), 0, Operation::SetPriceHistoryLength { length: 1 });
+
+        for second in 0..5u64 {
+            post_price(&mut contract, "BTC", 100 + u128::from(second), second * 1_000_000);
+        }
+
+        assert_eq!(contract.state.get_price_history(&btc()).blocking_wait().len(), 5);
+        let quote = contract.state.price_at(&btc(), Timestamp::from(2_500_000)).blocking_wait();
+        assert_eq!(quote.map(|quote| quote.timestamp), Some(Timestamp::from(2_000_000)));
+    }
+
+    #[test]
+    fn duel_without_a_fresh_end_quote_is_refunded() {
+        let mut contract = create_contract();
+        let operation = Operation::SetCircuitBreaker {
+            asset: btc(),
+            max_deviation_bps: None,
+            max_staleness_micros: Some(10_000_000),
+        };
+        execute(&mut contract, admin(), 0, operation);
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+        join_queue(&mut contract, player(11), tokens(10), 0);
+        let duel_id = last_duel_id(&contract);
+        predict(&mut contract, duel_id, 0);
+        post_price(&mut contract, "BTC", 100, 0);
+        execute(&mut contract, admin(), 0, Operation::StartDuel { duel_id, start_price: None });
+
+        // No quote arrives during the duel
+        execute(&mut contract, admin(), DUEL_DURATION_MICROS, Operation::ResolveDuel { duel_id, end_price: None });
+
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not completed");
+        assert_eq!(duel.status, DuelStatus::Cancelled);
+        assert_eq!(balance(&contract, player(10)), tokens(50));
+        assert_eq!(balance(&contract, player(11)), tokens(50));
+    }
+
+    #[test]
+    fn twap_settlement_is_chosen_per_duel_mode() {
+        let mut contract = create_contract();
+        let mode = SettlementMode::Twap { window_micros: 10_000_000 };
+        execute(&mut contract, admin(), 0, Operation::SetSettlementMode { duel_mode: DuelMode::Direction, mode });
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+        join_queue(&mut contract, player(11), tokens(10), 0);
+        let duel_id = last_duel_id(&contract);
+        predict(&mut contract, duel_id, 0);
+
+        // 100 for the first half of the window and 200 for the second
+        post_price(&mut contract, "BTC", 100, 0);
+        post_price(&mut contract, "BTC", 200, 5_000_000);
+        execute(&mut contract, admin(), 10_000_000, Operation::StartDuel { duel_id, start_price: None });
+
+        // A spike right at the end barely moves the average
+        let ends_at = 10_000_000 + DUEL_DURATION_MICROS;
+        post_price(&mut contract, "BTC", 1_000, ends_at - 1_000_000);
+        execute(&mut contract, admin(), ends_at, Operation::ResolveDuel { duel_id, end_price: None });
+
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not resolved");
+        assert_eq!(duel.settlement, mode);
+        assert_eq!(duel.start_twap, Some(Price::new(150_000_000, -6)));
+        assert_eq!(duel.end_twap, Some(Price::new(280_000_000, -6)));
+        assert_eq!(duel.winner, Some(player(10)));
+
+        // Other modes still settle on spot prices
+        assert_eq!(contract.state.get_settlement_mode(DuelMode::Range).blocking_wait(), SettlementMode::Spot);
+    }
+
+    #[test]
+    fn price_spike_halts_the_asset_and_holds_its_duels() {
+        let mut contract = create_contract();
+        let duel_id = hold_duel_for_review(&mut contract);
+
+        assert!(contract.state.get_asset_halt(&btc()).blocking_wait().is_some());
+        let duel = contract.state.get_duel(duel_id).blocking_wait().expect("Duel not found");
+        assert_eq!(duel.status, DuelStatus::PendingReview);
+        // The outlier is not recorded as the current price
+        let quote = contract.state.get_price(&btc()).blocking_wait().expect("No BTC quote");
+        assert_eq!(quote.price, Price::new(100_000_000, -6));
+    }
+
+    #[test]
+    fn cleared_duel_settles_once_the_breaker_is_reset() {
+        let mut contract = create_contract();
+        let duel_id = hold_duel_for_review(&mut contract);
+
+        execute(&mut contract, admin(), 20_000_000, Operation::ResetCircuitBreaker { asset: btc() });
+        execute(&mut contract, admin(), 20_000_000, Operation::ClearDuel { duel_id });
+        post_price(&mut contract, "BTC", 105, DUEL_DURATION_MICROS);
+        execute(&mut contract, admin(), DUEL_DURATION_MICROS, Operation::ResolveDuel { duel_id, end_price: None });
+
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not resolved");
+        assert_eq!(duel.status, DuelStatus::Resolved);
+        assert_eq!(duel.winner, Some(player(10)));
+    }
+
+    #[test]
+    fn voided_duel_refunds_every_stake() {
+        let mut contract = create_contract();
+        let duel_id = hold_duel_for_review(&mut contract);
+
+        execute(&mut contract, admin(), 20_000_000, Operation::VoidDuel { duel_id });
+
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not voided");
+        assert_eq!(duel.status, DuelStatus::Cancelled);
+        assert_eq!(balance(&contract, player(10)), tokens(50));
+        assert_eq!(balance(&contract, player(11)), tokens(50));
+    }
+
+    #[test]
+    #[should_panic(expected = "Duel is pending review")]
+    fn participants_cannot_cancel_a_duel_under_review() {
+        let mut contract = create_contract();
+        let duel_id = hold_duel_for_review(&mut contract);
+
+        let operation = Operation::CancelDuel { duel_id, reason: "test".to_string() };
+        execute(&mut contract, player(10), 20_000_000, operation);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only participants or the admin can cancel a duel")]
+    fn outsiders_cannot_cancel_a_duel() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+        join_queue(&mut contract, player(11), tokens(10), 0);
+
+        let operation = Operation::CancelDuel { duel_id: last_duel_id(&contract), reason: "test".to_string() };
+        execute(&mut contract, player(12), 0, operation);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duel not found")]
+    fn cancelled_duel_cannot_be_cancelled_again() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+        join_queue(&mut contract, player(11), tokens(10), 0);
+        let duel_id = last_duel_id(&contract);
+
+        let operation = Operation::CancelDuel { duel_id, reason: "test".to_string() };
+        execute(&mut contract, player(10), 0, operation.clone());
+        assert_eq!(balance(&contract, player(10)), tokens(50));
+        execute(&mut contract, player(10), 0, operation);
+    }
+
+    #[test]
+    fn closest_revealed_guess_wins() {
+        let mut contract = create_contract();
+        let duel_id = run_closest_price_duel(&mut contract);
+
+        let revealed_at = DUEL_DURATION_MICROS + 1_000_000;
+        reveal_guess(&mut contract, duel_id, player(10), 105, "salt-10", revealed_at);
+        reveal_guess(&mut contract, duel_id, player(11), 120, "salt-11", revealed_at);
+        execute(&mut contract, admin(), revealed_at, Operation::ResolveDuel { duel_id, end_price: None });
+
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not resolved");
+        assert_eq!(duel.winner, Some(player(10)));
+    }
+
+    #[test]
+    fn unrevealed_guess_forfeits_after_the_reveal_window() {
+        let mut contract = create_contract();
+        let duel_id = run_closest_price_duel(&mut contract);
+
+        reveal_guess(&mut contract, duel_id, player(11), 120, "salt-11", DUEL_DURATION_MICROS);
+        let closed_at = DUEL_DURATION_MICROS + REVEAL_WINDOW_MICROS + 1;
+        execute(&mut contract, admin(), closed_at, Operation::ResolveDuel { duel_id, end_price: None });
+
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not resolved");
+        assert_eq!(duel.winner, Some(player(11)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Reveal does not match commitment")]
+    fn reveal_must_match_the_commitment() {
+        let mut contract = create_contract();
+        let duel_id = run_closest_price_duel(&mut contract);
+
+        reveal_guess(&mut contract, duel_id, player(10), 108, "salt-10", DUEL_DURATION_MICROS);
+    }
+
+    #[test]
+    fn relative_duel_compares_returns_locked_from_the_oracle() {
+        let mut contract = create_contract();
+        let duel_id = match_mode(&mut contract, DuelMode::Relative, Some(Asset::new("ETH")));
+        for (player, leg) in [(player(10), Leg::Base), (player(11), Leg::Versus)] {
+            let operation = Operation::SubmitPrediction { duel_id, prediction: Prediction::Outperformer(leg) };
+            execute(&mut contract, player, 0, operation);
+        }
+
+        post_price(&mut contract, "BTC", 60_000, 0);
+        post_price(&mut contract, "ETH", 3_000, 0);
+        execute(&mut contract, admin(), 0, Operation::StartDuel { duel_id, start_price: None });
+
+        // BTC gains 5% while ETH gains 10%
+        post_price(&mut contract, "BTC", 63_000, DUEL_DURATION_MICROS);
+        post_price(&mut contract, "ETH", 3_300, DUEL_DURATION_MICROS);
+        execute(&mut contract, admin(), DUEL_DURATION_MICROS, Operation::ResolveDuel { duel_id, end_price: None });
+
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not resolved");
+        assert_eq!(duel.versus_start_price, Some(Price::new(3_000_000_000, -6)));
+        assert_eq!(duel.versus_end_price, Some(Price::new(3_300_000_000, -6)));
+        assert_eq!(duel.winner, Some(player(11)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Relative duels lock prices from oracle state")]
+    fn relative_duel_takes_no_manual_prices() {
+        let mut contract = create_contract();
+        let duel_id = match_mode(&mut contract, DuelMode::Relative, Some(Asset::new("ETH")));
+        for (player, leg) in [(player(10), Leg::Base), (player(11), Leg::Versus)] {
+            let operation = Operation::SubmitPrediction { duel_id, prediction: Prediction::Outperformer(leg) };
+            execute(&mut contract, player, 0, operation);
+        }
+
+        let start_price = Some(Price::new(60_000, 0));
+        execute(&mut contract, admin(), 0, Operation::StartDuel { duel_id, start_price });
+    }
+
+    #[test]
+    fn team_duel_splits_the_losing_stakes_between_winners() {
+        let mut contract = create_contract();
+        for n in 10..14 {
+            deposit(&mut contract, player(n), tokens(50));
+            join_team_queue(&mut contract, player(n));
+        }
+
+        let duel_id = last_duel_id(&contract);
+        let duel = contract.state.get_duel(duel_id).blocking_wait().expect("Team duel not created");
+        assert_eq!(duel.team_of(&player(11)), Some(Team::One));
+        assert_eq!(duel.team_of(&player(12)), Some(Team::Two));
+        assert_eq!(duel.opposing_pairs().len(), 4);
+
+        predict(&mut contract, duel_id, 0);
+        settle(&mut contract, duel_id, 110, 0);
+
+        // 40 token pot less a 2% fee on each stake, split evenly
+        for winner in [player(10), player(11)] {
+            assert_eq!(balance(&contract, winner), tokens(40).saturating_add(Amount::from_millis(19_600)));
+            assert_eq!(stats(&contract, winner).wins, 1);
+        }
+        for loser in [player(12), player(13)] {
+            assert_eq!(balance(&contract, loser), tokens(40));
+            assert_eq!(stats(&contract, loser).losses, 1);
+        }
+    }
+
+    #[test]
+    fn matchmaking_evicts_queued_players_who_can_no_longer_pay() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+        execute(&mut contract, player(10), 0, Operation::Withdraw { amount: tokens(45) });
+
+        join_queue(&mut contract, player(11), tokens(10), 0);
+
+        assert_eq!(queued_players(&contract), vec![player(11)]);
+        assert!(contract.state.get_duel(last_duel_id(&contract)).blocking_wait().is_none());
+    }
+
+    #[test]
+    fn matchmaking_checks_pair_caps_between_queued_opponents() {
+        let mut contract = create_contract();
+        let operation = Operation::SetCollusionLimits {
+            window_micros: 86_400_000_000,
+            flag_threshold: 10,
+            max_pair_matches: Some(1),
+            opposite_ratio_bps: None,
+        };
+        execute(&mut contract, admin(), 0, operation);
+        for n in 10..15 {
+            deposit(&mut contract, player(n), tokens(50));
+        }
+        play_duel(&mut contract, player(10), player(12), tokens(1), 0);
+
+        // Players 10 and 11 fill team one, so player 12 cannot join team two
+        for n in 10..14 {
+            join_team_queue(&mut contract, player(n));
+        }
+        assert_eq!(queued_players(&contract).len(), 4);
+
+        join_team_queue(&mut contract, player(14));
+        let duel = contract.state.get_duel(last_duel_id(&contract)).blocking_wait().expect("Team duel not created");
+        assert_eq!(duel.team_of(&player(10)), Some(Team::One));
+        assert_eq!(duel.team_of(&player(13)), Some(Team::Two));
+        assert!(!duel.is_participant(&player(12)));
+        assert_eq!(queued_players(&contract), vec![player(12)]);
+    }
+
+    #[test]
+    fn house_takes_waiting_bets_and_pays_its_winnings_to_the_pool() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(20), tokens(100));
+        execute(&mut contract, player(20), 0, Operation::ProvideLiquidity { amount: tokens(100) });
+        execute(&mut contract, admin(), 0, Operation::SetHouseConfig { enabled: true, match_after_micros: 10_000_000 });
+        execute(&mut contract, admin(), 0, Operation::SetHouseExposureLimit { asset: btc(), limit: tokens(50) });
+        deposit(&mut contract, player(10), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+
+        execute(&mut contract, admin(), 10_000_000, Operation::MatchWithHouse);
+        let duel_id = last_duel_id(&contract);
+        let duel = contract.state.get_duel(duel_id).blocking_wait().expect("House duel not created");
+        assert!(duel.is_house_duel());
+        assert_eq!(contract.state.get_house_exposure(&btc()).blocking_wait(), tokens(10));
+
+        // The house takes the opposite call and wins
+        let prediction = Prediction::Direction(Direction::Down);
+        execute(&mut contract, player(10), 10_000_000, Operation::SubmitPrediction { duel_id, prediction });
+        settle(&mut contract, duel_id, 110, 10_000_000);
+
+        // The pot less the player's rakeback stays with the pool
+        assert_eq!(balance(&contract, HOUSE_ACCOUNT), tokens(109).saturating_add(Amount::from_millis(980)));
+        assert_eq!(contract.state.get_house_exposure(&btc()).blocking_wait(), Amount::ZERO);
+        assert_eq!(stats(&contract, HOUSE_ACCOUNT).wins, 0);
+        assert_eq!(stats(&contract, player(10)).losses, 1);
+    }
+
+    #[test]
+    fn side_pool_pays_backers_of_the_winning_team_in_its_own_stats() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+        join_queue(&mut contract, player(11), tokens(10), 0);
+        let duel_id = last_duel_id(&contract);
+
+        let bets = [(player(20), Team::One, 3), (player(21), Team::One, 7), (player(22), Team::Two, 5)];
+        for (bettor, team, amount) in bets {
+            deposit(&mut contract, bettor, tokens(20));
+            execute(&mut contract, bettor, 0, Operation::PlaceSideBet { duel_id, team, amount: tokens(amount) });
+        }
+        predict(&mut contract, duel_id, 0);
+        settle(&mut contract, duel_id, 110, 0);
+
+        // The 5 token losing side less a 2% fee is split 3:7
+        assert_eq!(balance(&contract, player(20)), tokens(17).saturating_add(Amount::from_millis(4_470)));
+        assert_eq!(balance(&contract, player(21)), tokens(13).saturating_add(Amount::from_millis(10_430)));
+        assert_eq!(balance(&contract, player(22)), tokens(15));
+
+        let backer = stats(&contract, player(20));
+        assert_eq!((backer.side_bet_wins, backer.wins, backer.win_streak), (1, 0, 0));
+        assert_eq!(backer.side_bets_wagered, tokens(3));
+        assert_eq!(backer.total_wagered, Amount::ZERO);
+        let loser = stats(&contract, player(22));
+        assert_eq!((loser.side_bet_losses, loser.losses), (1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Side betting is closed for this duel")]
+    fn side_betting_closes_when_the_duel_starts() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        deposit(&mut contract, player(20), tokens(20));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+        join_queue(&mut contract, player(11), tokens(10), 0);
+        let duel_id = last_duel_id(&contract);
+        predict(&mut contract, duel_id, 0);
+        post_price(&mut contract, "BTC", 100, 0);
+        execute(&mut contract, admin(), 0, Operation::StartDuel { duel_id, start_price: None });
+
+        execute(&mut contract, player(20), 0, Operation::PlaceSideBet { duel_id, team: Team::One, amount: tokens(3) });
+    }
+
+    #[test]
+    fn jackpot_goes_to_the_first_player_to_reach_the_win_streak() {
+        let mut contract = create_contract();
+        execute(&mut contract, admin(), 0, Operation::SetJackpotConfig { fee_share_bps: 5_000, win_streak: 2 });
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        play_duel(&mut contract, player(10), player(11), tokens(10), 0);
+        // Half of the 0.4 token fee feeds the jackpot
+        assert_eq!(*contract.state.jackpot_pool.get(), Amount::from_millis(200));
+        assert_eq!(stats(&contract, player(10)).duel_win_streak, 1);
+        assert_eq!(stats(&contract, player(11)).duel_win_streak, 0);
+
+        let duel_id = play_duel(&mut contract, player(10), player(11), tokens(10), 2 * DUEL_DURATION_MICROS);
+
+        assert_eq!(*contract.state.jackpot_pool.get(), Amount::ZERO);
+        let wins = contract.state.jackpot_wins.elements().blocking_wait().unwrap_or_default();
+        assert_eq!(wins.len(), 1);
+        assert_eq!((wins[0].player, wins[0].duel_id, wins[0].win_streak), (player(10), duel_id, 2));
+        assert_eq!(wins[0].amount, Amount::from_millis(400));
+    }
+
+    #[test]
+    fn a_loss_resets_the_jackpot_streak() {
+        let mut contract = create_contract();
+        execute(&mut contract, admin(), 0, Operation::SetJackpotConfig { fee_share_bps: 5_000, win_streak: 2 });
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        play_duel(&mut contract, player(10), player(11), tokens(10), 0);
+        play_duel(&mut contract, player(11), player(10), tokens(10), 2 * DUEL_DURATION_MICROS);
+        play_duel(&mut contract, player(10), player(11), tokens(10), 4 * DUEL_DURATION_MICROS);
+
+        assert_eq!(stats(&contract, player(10)).duel_win_streak, 1);
+        assert!(contract.state.jackpot_wins.elements().blocking_wait().unwrap_or_default().is_empty());
+        assert_eq!(*contract.state.jackpot_pool.get(), Amount::from_millis(600));
+    }
+
+    #[test]
+    fn referrer_earns_a_share_of_the_fee_on_referred_stakes() {
+        let mut contract = create_contract();
+        execute(&mut contract, player(10), 0, Operation::RegisterReferrer { referrer: player(30) });
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        play_duel(&mut contract, player(10), player(11), tokens(10), 0);
+
+        // 10% of the 0.2 token fee on player 10's stake
+        assert_eq!(balance(&contract, player(30)), Amount::from_millis(20));
+        assert_eq!(contract.state.get_referral_earnings(&player(30)).blocking_wait(), Amount::from_millis(20));
+        // The platform keeps the fee less the referral and both players' rakeback
+        assert_eq!(*contract.state.total_fees.get(), Amount::from_millis(340));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot refer yourself")]
+    fn players_cannot_refer_themselves() {
+        let mut contract = create_contract();
+        execute(&mut contract, player(10), 0, Operation::RegisterReferrer { referrer: player(10) });
+    }
+
+    #[test]
+    fn fee_tier_follows_the_volume_each_player_has_wagered() {
+        let mut contract = create_contract();
+        let tiers = vec![FeeTier { min_wagered: tokens(15), fee_bps: 100 }];
+        execute(&mut contract, admin(), 0, Operation::SetFeeTiers { tiers });
+        for n in 10..13 {
+            deposit(&mut contract, player(n), tokens(50));
+        }
+
+        play_duel(&mut contract, player(10), player(12), tokens(10), 0);
+        let duel_id = play_duel(&mut contract, player(10), player(11), tokens(10), 2 * DUEL_DURATION_MICROS);
+
+        // Player 10 has wagered 20 tokens and pays 1%, player 11 only 10 and pays 2%
+        let duel = contract.state.get_recent_duel(duel_id).blocking_wait().expect("Duel not resolved");
+        let fees: Vec<_> = duel.participants.iter().map(|p| (p.player, p.fee)).collect();
+        assert_eq!(fees, vec![(player(10), Amount::from_millis(100)), (player(11), Amount::from_millis(200))]);
+        assert_eq!(duel.total_fee(), Amount::from_millis(300));
+    }
+
+    #[test]
+    fn rakeback_is_claimable_again_once_a_new_epoch_starts() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+        let day = RAKEBACK_EPOCH_MICROS;
+
+        play_duel(&mut contract, player(10), player(11), tokens(10), 0);
+        let before = balance(&contract, player(11));
+        execute(&mut contract, player(11), day - 1_000_000, Operation::ClaimRakeback);
+        // 10% of the 0.2 token fee
+        assert_eq!(balance(&contract, player(11)), before.saturating_add(Amount::from_millis(20)));
+
+        // Claimed again a minute into the next epoch, well within a day of the last claim
+        play_duel(&mut contract, player(10), player(11), tokens(10), day - 1_000_000);
+        execute(&mut contract, player(11), day + DUEL_DURATION_MICROS, Operation::ClaimRakeback);
+        let account = contract.state.get_rakeback_account(&player(11)).blocking_wait();
+        assert_eq!(account.last_claimed_epoch, Some(1));
+        assert_eq!(account.accrued, Amount::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rakeback already claimed this epoch")]
+    fn rakeback_is_claimable_once_per_epoch() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        play_duel(&mut contract, player(10), player(11), tokens(10), 0);
+        execute(&mut contract, player(11), 2 * DUEL_DURATION_MICROS, Operation::ClaimRakeback);
+        play_duel(&mut contract, player(10), player(11), tokens(10), 2 * DUEL_DURATION_MICROS);
+        execute(&mut contract, player(11), 4 * DUEL_DURATION_MICROS, Operation::ClaimRakeback);
+    }
+
+    #[test]
+    #[should_panic(expected = "Loss limit reached")]
+    fn loss_limit_blocks_stakes_that_could_exceed_it() {
+        let mut contract = create_contract();
+        set_daily_limit(&mut contract, player(11), LimitKind::Loss, tokens(15));
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        play_duel(&mut contract, player(10), player(11), tokens(10), 0);
+        join_queue(&mut contract, player(11), tokens(10), 2 * DUEL_DURATION_MICROS);
+    }
+
+    #[test]
+    #[should_panic(expected = "Deposit limit reached")]
+    fn deposit_limit_caps_deposits_over_the_period() {
+        let mut contract = create_contract();
+        set_daily_limit(&mut contract, player(10), LimitKind::Deposit, tokens(20));
+        execute(&mut contract, player(10), 1_000_000, Operation::Deposit { amount: tokens(15) });
+        execute(&mut contract, player(10), 2_000_000, Operation::Deposit { amount: tokens(10) });
+    }
+
+    #[test]
+    fn self_exclusion_leaves_the_queue_and_ends_on_schedule() {
+        let mut contract = create_contract();
+        let day = 86_400_000_000;
+        deposit(&mut contract, player(10), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+
+        let until = Timestamp::from(day);
+        execute(&mut contract, player(10), 1_000_000, Operation::SelfExclude { until });
+        assert!(queued_players(&contract).is_empty());
+
+        join_queue(&mut contract, player(10), tokens(10), day);
+        assert_eq!(queued_players(&contract), vec![player(10)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Self-excluded until")]
+    fn self_excluded_players_cannot_join_the_queue() {
+        let mut contract = create_contract();
+        let day = 86_400_000_000;
+        deposit(&mut contract, player(10), tokens(50));
+        let until = Timestamp::from(day);
+        execute(&mut contract, player(10), 1_000_000, Operation::SelfExclude { until });
+        join_queue(&mut contract, player(10), tokens(10), day - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Self-exclusion cannot be shortened")]
+    fn self_exclusion_cannot_be_shortened() {
+        let mut contract = create_contract();
+        let day = 86_400_000_000;
+        execute(&mut contract, player(10), 1_000_000, Operation::SelfExclude { until: Timestamp::from(2 * day) });
+        execute(&mut contract, player(10), 2_000_000, Operation::SelfExclude { until: Timestamp::from(day) });
+    }
+
+    #[test]
+    #[should_panic(expected = "Rate limit exceeded")]
+    fn queue_joins_and_leaves_are_rate_limited() {
+        let mut contract = create_contract();
+        let operation = Operation::SetQueueRateLimit { max_ops: 2, window_micros: DUEL_DURATION_MICROS };
+        execute(&mut contract, admin(), 0, operation);
+        deposit(&mut contract, player(10), tokens(50));
+
+        join_queue(&mut contract, player(10), tokens(10), 1_000_000);
+        execute(&mut contract, player(10), 2_000_000, Operation::LeaveQueue);
+        join_queue(&mut contract, player(10), tokens(10), 3_000_000);
+    }
+
+    #[test]
+    fn queue_rate_limit_resets_each_window() {
+        let mut contract = create_contract();
+        let operation = Operation::SetQueueRateLimit { max_ops: 2, window_micros: DUEL_DURATION_MICROS };
+        execute(&mut contract, admin(), 0, operation);
+        deposit(&mut contract, player(10), tokens(50));
+
+        join_queue(&mut contract, player(10), tokens(10), 1_000_000);
+        execute(&mut contract, player(10), 2_000_000, Operation::LeaveQueue);
+        join_queue(&mut contract, player(10), tokens(10), 1_000_000 + DUEL_DURATION_MICROS);
+        assert_eq!(queued_players(&contract), vec![player(10)]);
+    }
+
+    #[test]
+    fn pairs_meeting_too_often_are_flagged_until_cleared() {
+        let mut contract = create_contract();
+        set_collusion_limits(&mut contract, 86_400_000_000, 2, None);
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        play_duel(&mut contract, player(10), player(11), tokens(1), 0);
+        let stats = contract.state.get_pair_stats(&player(10), &player(11)).blocking_wait();
+        assert_eq!(stats.flagged_at, None);
+
+        let time = 2 * DUEL_DURATION_MICROS;
+        play_duel(&mut contract, player(11), player(10), tokens(1), time);
+        let stats = contract.state.get_pair_stats(&player(10), &player(11)).blocking_wait();
+        assert_eq!(stats.window_duels, 2);
+        assert_eq!(stats.flagged_at, Some(Timestamp::from(time)));
+
+        let operation = Operation::ClearPairFlag { player_a: player(11), player_b: player(10) };
+        execute(&mut contract, admin(), time, operation);
+        let stats = contract.state.get_pair_stats(&player(10), &player(11)).blocking_wait();
+        assert_eq!(stats.flagged_at, None);
+    }
+
+    #[test]
+    fn pairs_almost_always_calling_opposite_sides_are_flagged() {
+        let mut contract = create_contract();
+        // A one microsecond window keeps the duel count below the threshold
+        set_collusion_limits(&mut contract, 1, 2, None);
+        deposit(&mut contract, player(10), tokens(50));
+        deposit(&mut contract, player(11), tokens(50));
+
+        play_duel(&mut contract, player(10), player(11), tokens(1), 0);
+        play_duel(&mut contract, player(10), player(11), tokens(1), 2 * DUEL_DURATION_MICROS);
+
+        let stats = contract.state.get_pair_stats(&player(10), &player(11)).blocking_wait();
+        assert_eq!(stats.window_duels, 1);
+        assert_eq!((stats.settled, stats.opposite_predictions), (2, 2));
+        assert_eq!(stats.flagged_at, Some(Timestamp::from(3 * DUEL_DURATION_MICROS)));
+    }
+
+    #[test]
+    fn pair_match_limit_keeps_capped_pairs_apart() {
+        let mut contract = create_contract();
+        set_collusion_limits(&mut contract, 86_400_000_000, 10, Some(1));
+        for n in 10..13 {
+            deposit(&mut contract, player(n), tokens(50));
+        }
+        let first = play_duel(&mut contract, player(10), player(11), tokens(1), 0);
+
+        let time = 2 * DUEL_DURATION_MICROS;
+        join_queue(&mut contract, player(10), tokens(1), time);
+        join_queue(&mut contract, player(11), tokens(1), time);
+        assert_eq!(last_duel_id(&contract), first);
+        assert_eq!(queued_players(&contract), vec![player(10), player(11)]);
+
+        // Another opponent is matched as usual
+        join_queue(&mut contract, player(12), tokens(1), time);
+        let duel = contract.state.get_duel(last_duel_id(&contract)).blocking_wait().expect("Duel not created");
+        assert!(duel.is_participant(&player(10)) && duel.is_participant(&player(12)));
+        assert_eq!(queued_players(&contract), vec![player(11)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pair is not flagged")]
+    fn clearing_an_unflagged_pair_is_rejected() {
+        let mut contract = create_contract();
+        let operation = Operation::ClearPairFlag { player_a: player(10), player_b: player(11) };
+        execute(&mut contract, admin(), 0, operation);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account is blocked: chargeback")]
+    fn blocked_accounts_cannot_join_the_queue() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        join_queue(&mut contract, player(10), tokens(10), 0);
+
+        let operation = Operation::BlockAccount { account: player(10), reason: "chargeback".to_string() };
+        execute(&mut contract, admin(), 1_000_000, operation);
+        assert!(queued_players(&contract).is_empty());
+        join_queue(&mut contract, player(10), tokens(10), 2_000_000);
+    }
+
+    #[test]
+    fn unblocked_accounts_can_play_again() {
+        let mut contract = create_contract();
+        deposit(&mut contract, player(10), tokens(50));
+        let operation = Operation::BlockAccount { account: player(10), reason: "chargeback".to_string() };
+        execute(&mut contract, admin(), 0, operation);
+        execute(&mut contract, admin(), 0, Operation::UnblockAccount { account: player(10) });
+
+        assert!(contract.state.get_account_block(&player(10)).blocking_wait().is_none());
+        join_queue(&mut contract, player(10), tokens(10), 0);
+        assert_eq!(queued_players(&contract), vec![player(10)]);
+    }
+
+    #[test]
+    fn blocked_tournament_players_forfeit_their_match() {
+        let mut contract = create_contract();
+        let operation = Operation::CreateTournament {
+            asset: btc(),
+            entry_fee: tokens(5),
+            duration_micros: DUEL_DURATION_MICROS,
+            size: 2,
+            prize_split: vec![10000],
+        };
+        execute(&mut contract, admin(), 0, operation);
+        let tournament_id = TournamentId(1);
+        for n in [10, 11] {
+            deposit(&mut contract, player(n), tokens(50));
+        }
+
+        execute(&mut contract, player(10), 0, Operation::RegisterTournament { tournament_id });
+        let operation = Operation::BlockAccount { account: player(10), reason: "multi-accounting".to_string() };
+        execute(&mut contract, admin(), 0, operation);
+        execute(&mut contract, player(11), 0, Operation::RegisterTournament { tournament_id });
+
+        let tournament = contract.state.get_tournament(tournament_id).blocking_wait().expect("Tournament not found");
+        let first = &tournament.bracket[0];
+        assert_eq!(first.duel_id, None);
+        assert_eq!(first.winner, Some(player(11)));
+        assert_eq!(tournament.status, TournamentStatus::Completed);
+    }
+}
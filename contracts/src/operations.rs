@@ -1,7 +1,7 @@
 //! Operations and messages for SpeedBet Arena
 
 use crate::types::*;
-use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId, CryptoHash, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// Arguments provided when instantiating the application
@@ -40,6 +40,158 @@ pub enum Operation {
         max_bet: Option<Amount>,
     },
 
+    /// Set the volume-based fee tiers (admin only)
+    SetFeeTiers {
+        /// Tiers by lifetime amount wagered, lowest threshold first (empty for a flat fee)
+        tiers: Vec<FeeTier>,
+    },
+
+    /// Set the bucket edge and tiered payouts of new range duels (admin only)
+    SetRangeBuckets {
+        /// Move separating small from large buckets in basis points
+        edge_bps: u64,
+        /// Share of the losing stakes the winner takes in basis points, by buckets missed
+        payout_bps: Vec<u16>,
+    },
+
+    /// Set the duel durations players may choose from (admin only)
+    SetDuelDurations {
+        /// Allowed durations in microseconds
+        durations: Vec<u64>,
+    },
+
+    /// List a new asset or update a listed one, enabling it (admin only)
+    ListAsset {
+        /// Asset symbol (e.g. "SOL")
+        symbol: String,
+        /// Number of decimals to show in the UI
+        display_decimals: u8,
+        /// Currency prices are quoted in (e.g. "USD")
+        quote_currency: String,
+        /// Number of decimals prices are stored with
+        price_decimals: u8,
+        /// Minimum bet (defaults to the platform minimum)
+        min_bet: Option<Amount>,
+        /// Maximum bet (defaults to the platform maximum)
+        max_bet: Option<Amount>,
+        /// Accounts allowed to report prices (anyone if empty)
+        oracle_sources: Vec<AccountOwner>,
+    },
+
+    /// Enable or disable new bets on a listed asset (admin only)
+    SetAssetEnabled {
+        /// Asset to update
+        asset: Asset,
+        /// Whether new bets are accepted
+        enabled: bool,
+    },
+
+    /// Set how new duels of a mode are settled (admin only)
+    SetSettlementMode {
+        /// Duel mode to update
+        duel_mode: DuelMode,
+        /// Spot or TWAP settlement
+        mode: SettlementMode,
+    },
+
+    /// Configure the circuit breaker of a listed asset (admin only)
+    SetCircuitBreaker {
+        /// Asset to update
+        asset: Asset,
+        /// Largest move from the previous quote in basis points (unchecked if None)
+        max_deviation_bps: Option<u64>,
+        /// Longest gap between quotes in microseconds (unchecked if None)
+        max_staleness_micros: Option<u64>,
+    },
+
+    /// Lift a tripped circuit breaker so the asset trades again (admin only)
+    ResetCircuitBreaker {
+        /// Halted asset
+        asset: Asset,
+    },
+
+    /// Return a duel held for review to play (admin only)
+    ClearDuel {
+        /// Duel to clear
+        duel_id: DuelId,
+    },
+
+    /// Void a duel held for review, refunding both players (admin only)
+    VoidDuel {
+        /// Duel to void
+        duel_id: DuelId,
+    },
+
+    /// Set how often each player may join or leave the queue (admin only)
+    SetQueueRateLimit {
+        /// Joins and leaves allowed per window
+        max_ops: u32,
+        /// Window length in microseconds
+        window_micros: u64,
+    },
+
+    /// Configure collusion detection between repeat opponents (admin only)
+    SetCollusionLimits {
+        /// Window for counting duels between the same pair in microseconds
+        window_micros: u64,
+        /// Duels between the same pair within a window that flag it
+        flag_threshold: u32,
+        /// Most duels matched between the same pair per window (unlimited if None)
+        max_pair_matches: Option<u32>,
+        /// Share of settled duels with opposite predictions that flags a pair in basis points (off if None)
+        opposite_ratio_bps: Option<u16>,
+    },
+
+    /// Clear the suspicious flag on a pair of players (admin only)
+    ClearPairFlag {
+        /// First player
+        player_a: AccountOwner,
+        /// Second player
+        player_b: AccountOwner,
+    },
+
+    /// Set how many quotes are kept per asset in the price history (admin only)
+    SetPriceHistoryLength {
+        /// Number of quotes to keep
+        length: u32,
+    },
+
+    /// Configure rakeback (admin only)
+    SetRakeback {
+        /// Share of fees paid returned in basis points
+        rakeback_bps: u16,
+        /// Epoch length in microseconds (minimum time between claims)
+        epoch_micros: u64,
+    },
+
+    /// Configure the progressive jackpot (admin only)
+    SetJackpotConfig {
+        /// Share of each duel fee fed into the jackpot in basis points
+        fee_share_bps: u16,
+        /// Win streak that wins the jackpot
+        win_streak: u64,
+    },
+
+    /// Set the share of fees paid to referrers (admin only)
+    SetReferralFeeShare {
+        /// Share of a referred player's fees in basis points
+        fee_share_bps: u16,
+    },
+
+    /// Block an account from queueing, predicting and new matches (admin only)
+    BlockAccount {
+        /// Account to block
+        account: AccountOwner,
+        /// Reason for the block
+        reason: String,
+    },
+
+    /// Lift an account block (admin only)
+    UnblockAccount {
+        /// Blocked account
+        account: AccountOwner,
+    },
+
     /// Pause/unpause platform (admin only)
     SetPaused {
         /// Whether to pause
@@ -60,6 +212,31 @@ pub enum Operation {
         amount: Amount,
     },
 
+    /// Lock the caller out of betting until a time; cannot be shortened
+    SelfExclude {
+        /// When the exclusion ends
+        until: Timestamp,
+    },
+
+    /// Set, raise or remove one of the caller's responsible-gaming limits
+    SetGamingLimit {
+        /// What the limit caps
+        kind: LimitKind,
+        /// Rolling period the limit applies to
+        period: LimitPeriod,
+        /// New limit (removed if None); raising or removing waits out a cooling-off delay
+        amount: Option<Amount>,
+    },
+
+    /// Claim rakeback on fees paid (once per epoch)
+    ClaimRakeback,
+
+    /// Register the player who referred the caller (once)
+    RegisterReferrer {
+        /// Referrer address
+        referrer: AccountOwner,
+    },
+
     // ============ MATCHMAKING OPERATIONS ============
 
     /// Join the matchmaking queue
@@ -68,6 +245,14 @@ pub enum Operation {
         asset: Asset,
         /// Bet amount
         bet_amount: Amount,
+        /// Duel duration in microseconds (from the configured list)
+        duration_micros: u64,
+        /// Players per team (1 for a solo duel)
+        team_size: u8,
+        /// Kind of prediction to make
+        mode: DuelMode,
+        /// Asset to compare against (relative duels only)
+        versus_asset: Option<Asset>,
     },
 
     /// Leave the matchmaking queue
@@ -79,27 +264,45 @@ pub enum Operation {
     SubmitPrediction {
         /// Duel ID
         duel_id: DuelId,
-        /// Prediction direction
-        direction: Direction,
+        /// Direction or price-change bucket, matching the duel mode
+        prediction: Prediction,
+    },
+
+    /// Commit to a hidden target price in a closest-price duel
+    CommitGuess {
+        /// Duel ID
+        duel_id: DuelId,
+        /// Hash of the `GuessReveal`
+        commitment: CryptoHash,
+    },
+
+    /// Reveal a committed target price once the duel has started
+    RevealGuess {
+        /// Duel ID
+        duel_id: DuelId,
+        /// Committed target price
+        target: Price,
+        /// Salt used in the commitment
+        salt: String,
     },
 
     /// Start the duel (called after both predict)
     StartDuel {
         /// Duel ID
         duel_id: DuelId,
-        /// Starting price
-        start_price: u64,
+        /// Starting price (taken from the oracle history if omitted, held to the circuit breaker if given)
+        start_price: Option<Price>,
     },
 
-    /// Resolve the duel (called after 60 seconds)
+    /// Resolve the duel (called after the duel's configured duration)
     ResolveDuel {
         /// Duel ID
         duel_id: DuelId,
-        /// Ending price
-        end_price: u64,
+        /// Ending price (taken from the oracle history at the duel's end if omitted, held to the circuit breaker if given)
+        end_price: Option<Price>,
     },
 
-    /// Cancel a duel (timeout or dispute)
+    /// Cancel a duel (participants or admin; duels under review are voided by the admin)
     CancelDuel {
         /// Duel ID
         duel_id: DuelId,
@@ -107,6 +310,149 @@ pub enum Operation {
         reason: String,
     },
 
+    /// Offer the opponent a rematch of a resolved duel
+    OfferRematch {
+        /// Resolved duel ID
+        duel_id: DuelId,
+        /// Whether to double the stake for the rematch
+        double_stake: bool,
+    },
+
+    /// Accept a pending rematch offer
+    AcceptRematch {
+        /// Resolved duel ID the offer was made for
+        duel_id: DuelId,
+    },
+
+    /// Back one team of a duel that has not started yet
+    PlaceSideBet {
+        /// Duel ID
+        duel_id: DuelId,
+        /// Team to back
+        team: Team,
+        /// Stake amount
+        amount: Amount,
+    },
+
+    // ============ SERIES OPERATIONS ============
+
+    /// Challenge another player to a best-of-N series
+    ChallengeSeries {
+        /// Challenged player
+        opponent: AccountOwner,
+        /// Asset to bet on
+        asset: Asset,
+        /// Series stake per player
+        stake: Amount,
+        /// Maximum number of duels (odd)
+        best_of: u32,
+        /// Duration of each duel in microseconds (from the configured list)
+        duration_micros: u64,
+    },
+
+    /// Accept a series challenge and escrow the stake
+    AcceptSeries {
+        /// Series ID
+        series_id: SeriesId,
+    },
+
+    /// Withdraw or decline a pending series challenge
+    CancelSeries {
+        /// Series ID
+        series_id: SeriesId,
+    },
+
+    // ============ TOURNAMENT OPERATIONS ============
+
+    /// Create a single-elimination tournament (admin only)
+    CreateTournament {
+        /// Asset to bet on
+        asset: Asset,
+        /// Entry fee per player
+        entry_fee: Amount,
+        /// Duration of each duel in microseconds (from the configured list)
+        duration_micros: u64,
+        /// Number of players (power of two)
+        size: u32,
+        /// Prize pool share per placement tier in basis points (champion first)
+        prize_split: Vec<u16>,
+    },
+
+    /// Cancel a tournament that has not started and refund entry fees (admin only)
+    CancelTournament {
+        /// Tournament ID
+        tournament_id: TournamentId,
+    },
+
+    /// Register for a tournament and pay the entry fee
+    RegisterTournament {
+        /// Tournament ID
+        tournament_id: TournamentId,
+    },
+
+    // ============ POOL OPERATIONS ============
+
+    /// Set the pool round staking and running interval (admin only)
+    SetPoolInterval {
+        /// Interval in microseconds
+        interval_micros: u64,
+    },
+
+    /// Stake on UP or DOWN in the current pool round for an asset
+    JoinPool {
+        /// Asset to bet on
+        asset: Asset,
+        /// Predicted direction
+        direction: Direction,
+        /// Stake amount
+        amount: Amount,
+    },
+
+    /// Lock the start price of a pool round once staking has closed
+    LockPoolRound {
+        /// Round ID
+        round_id: RoundId,
+    },
+
+    /// Resolve a locked pool round and pay out the winning side
+    ResolvePoolRound {
+        /// Round ID
+        round_id: RoundId,
+    },
+
+    // ============ HOUSE OPERATIONS ============
+
+    /// Configure house matching (admin only)
+    SetHouseConfig {
+        /// Whether the house matches players left waiting
+        enabled: bool,
+        /// How long a player waits before the house takes the other side
+        match_after_micros: u64,
+    },
+
+    /// Set how much the house may stake on an asset at once (admin only)
+    SetHouseExposureLimit {
+        /// Asset to update
+        asset: Asset,
+        /// Exposure limit (house off for the asset if zero)
+        limit: Amount,
+    },
+
+    /// Add liquidity to the house pool in exchange for LP shares
+    ProvideLiquidity {
+        /// Amount to add from the caller's balance
+        amount: Amount,
+    },
+
+    /// Redeem LP shares for their share of the house pool
+    WithdrawLiquidity {
+        /// Shares to redeem
+        shares: u128,
+    },
+
+    /// Let the house take the other side for players who waited long enough
+    MatchWithHouse,
+
     // ============ ORACLE OPERATIONS ============
 
     /// Update price data (oracle/admin)
@@ -114,7 +460,7 @@ pub enum Operation {
         /// Asset to update
         asset: Asset,
         /// New price
-        price: u64,
+        price: Price,
     },
 }
 
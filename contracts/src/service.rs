@@ -8,7 +8,7 @@ use std::sync::Arc;
 use async_graphql::{EmptySubscription, Object, Schema, SimpleObject, Request, Response};
 use linera_sdk::{
     abi::WithServiceAbi,
-    linera_base_types::Amount,
+    linera_base_types::{AccountOwner, Amount, Timestamp},
     views::View,
     Service, ServiceRuntime,
 };
@@ -68,20 +68,397 @@ impl Service for SpeedBetService {
             .into_iter()
             .map(|entry| QueueEntryGQL {
                 player: format!("{:?}", entry.player),
-                asset: format!("{:?}", entry.asset),
+                asset: entry.asset.to_string(),
                 bet_amount: entry.bet_amount.to_string(),
+                duration_micros: entry.duration_micros.to_string(),
+                team_size: entry.team_size,
+                mode: format!("{:?}", entry.mode),
+                versus_asset: entry.versus_asset.as_ref().map(|a| a.to_string()),
                 joined_at: entry.joined_at.micros().to_string(),
             })
             .collect();
 
+        let now = self.runtime.system_time();
+
+        // Convert pending rematch offers to GraphQL format, leaving out expired ones
+        let rematch_offers: Vec<RematchOfferGQL> = self.state.rematch_offers.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, offer)| !offer.is_expired(now))
+            .map(|(_, offer)| RematchOfferGQL {
+                duel_id: offer.duel_id.to_string(),
+                offered_by: format!("{:?}", offer.offered_by),
+                opponent: format!("{:?}", offer.opponent),
+                asset: offer.asset.to_string(),
+                bet_amount: offer.bet_amount.to_string(),
+                duration_micros: offer.duration_micros.to_string(),
+                mode: format!("{:?}", offer.mode),
+                versus_asset: offer.versus_asset.as_ref().map(|a| a.to_string()),
+                offered_at: offer.offered_at.micros().to_string(),
+            })
+            .collect();
+
+        // Convert series to GraphQL format
+        let series: Vec<SeriesInfoGQL> = self.state.series.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, series)| SeriesInfoGQL {
+                id: series.id.to_string(),
+                player1: format!("{:?}", series.player1),
+                player2: format!("{:?}", series.player2),
+                asset: series.asset.to_string(),
+                stake: series.stake.to_string(),
+                best_of: series.best_of,
+                duration_micros: series.duration_micros.to_string(),
+                wins_needed: series.wins_needed(),
+                duel_ids: series.duel_ids.iter().map(|id| id.to_string()).collect(),
+                p1_wins: series.p1_wins,
+                p2_wins: series.p2_wins,
+                status: format!("{:?}", series.status),
+                winner: series.winner.map(|w| format!("{:?}", w)),
+                created_at: series.created_at.micros().to_string(),
+            })
+            .collect();
+
+        // Convert tournaments and their brackets to GraphQL format
+        let tournaments: Vec<TournamentInfoGQL> = self.state.tournaments.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, tournament)| TournamentInfoGQL {
+                id: tournament.id.to_string(),
+                asset: tournament.asset.to_string(),
+                entry_fee: tournament.entry_fee.to_string(),
+                duration_micros: tournament.duration_micros.to_string(),
+                size: tournament.size,
+                prize_split: tournament.prize_split.clone(),
+                players: tournament.players.iter().map(|p| format!("{:?}", p)).collect(),
+                bracket: tournament.bracket.iter().map(|m| BracketMatchGQL {
+                    round: m.round,
+                    slot: m.slot,
+                    player1: format!("{:?}", m.player1),
+                    player2: format!("{:?}", m.player2),
+                    duel_id: m.duel_id.map(|id| id.to_string()),
+                    winner: m.winner.map(|w| format!("{:?}", w)),
+                }).collect(),
+                current_round: tournament.current_round,
+                rounds: tournament.rounds(),
+                status: format!("{:?}", tournament.status),
+                prize_pool: tournament.prize_pool.to_string(),
+                champion: tournament.champion.map(|c| format!("{:?}", c)),
+                created_at: tournament.created_at.micros().to_string(),
+            })
+            .collect();
+
+        // Convert pool rounds to GraphQL format
+        let pool_rounds: Vec<PoolRoundGQL> = self.state.pool_rounds.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, round)| PoolRoundGQL::from(&round))
+            .collect();
+        let recent_pool_rounds: Vec<PoolRoundGQL> = self.state.recent_pool_rounds.elements().await
+            .unwrap_or_default()
+            .iter()
+            .map(PoolRoundGQL::from)
+            .collect();
+
+        // Convert spectator side pools to GraphQL format
+        // Duels in progress and recently completed
+        let active_duels: Vec<DuelInfoGQL> = self.state.active_duels.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, duel)| DuelInfoGQL::from(&duel))
+            .collect();
+        let recent_duels: Vec<DuelInfoGQL> = self.state.recent_duels.elements().await
+            .unwrap_or_default()
+            .iter()
+            .map(DuelInfoGQL::from)
+            .collect();
+
+        let side_pools: Vec<SidePoolGQL> = self.state.side_pools.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, pool)| SidePoolGQL::from(&pool))
+            .collect();
+        let recent_side_pools: Vec<SidePoolGQL> = self.state.recent_side_pools.elements().await
+            .unwrap_or_default()
+            .iter()
+            .map(SidePoolGQL::from)
+            .collect();
+
+        // Convert per-mode settlement to GraphQL format
+        let settlement_modes: Vec<SettlementModeGQL> = self.state.settlement_modes.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(duel_mode, settlement)| SettlementModeGQL {
+                duel_mode: format!("{:?}", duel_mode),
+                settlement: settlement_name(settlement),
+                twap_window_micros: twap_window(settlement),
+            })
+            .collect();
+
+        // Convert asset registry to GraphQL format
+        let assets: Vec<AssetConfigGQL> = self.state.assets.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, config)| AssetConfigGQL {
+                symbol: config.asset.to_string(),
+                display_decimals: config.display_decimals,
+                quote_currency: config.quote_currency.clone(),
+                price_decimals: config.price_decimals,
+                enabled: config.enabled,
+                min_bet: config.min_bet.to_string(),
+                max_bet: config.max_bet.to_string(),
+                oracle_sources: config.oracle_sources.iter().map(|o| format!("{:?}", o)).collect(),
+                max_deviation_bps: config.max_deviation_bps.map(|bps| bps.to_string()),
+                max_staleness_micros: config.max_staleness_micros.map(|micros| micros.to_string()),
+                house_exposure_limit: config.house_exposure_limit.to_string(),
+            })
+            .collect();
+
+        // Convert per-asset price history to GraphQL format
+        let mut price_history = Vec::new();
+        for (asset, quotes) in self.state.price_history.index_values().await.unwrap_or_default() {
+            let quote_currency = self.state.get_asset_config(&asset).await
+                .map(|config| config.quote_currency)
+                .unwrap_or_default();
+            price_history.push(PriceHistoryGQL {
+                asset: asset.to_string(),
+                quotes: quotes.iter().map(|quote| PriceDataGQL {
+                    asset: quote.asset.to_string(),
+                    price: quote.price.to_string(),
+                    quote_currency: quote_currency.clone(),
+                    timestamp: quote.timestamp.micros().to_string(),
+                }).collect(),
+            });
+        }
+
+        // Convert circuit breaker halts to GraphQL format
+        let halted_assets: Vec<AssetHaltGQL> = self.state.halted_assets.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, halt)| AssetHaltGQL {
+                asset: halt.asset.to_string(),
+                reason: halt.reason,
+                price: halt.price.to_string(),
+                halted_at: halt.halted_at.micros().to_string(),
+            })
+            .collect();
+
+        // Convert house pool state to GraphQL format
+        let pool_value = self.state.house_pool_value().await;
+        let total_shares = *self.state.total_lp_shares.get();
+        let mut house_exposure = Vec::new();
+        for (asset, amount) in self.state.house_exposure.index_values().await.unwrap_or_default() {
+            let limit = self.state.get_asset_config(&asset).await
+                .map(|config| config.house_exposure_limit)
+                .unwrap_or_default();
+            house_exposure.push(HouseExposureGQL {
+                asset: asset.to_string(),
+                amount: amount.to_string(),
+                limit: limit.to_string(),
+            });
+        }
+        let liquidity_providers: Vec<LpPositionGQL> = self.state.lp_shares.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(provider, shares)| LpPositionGQL {
+                provider: format!("{:?}", provider),
+                shares: shares.to_string(),
+                value: lp_share_value(shares, pool_value, total_shares).to_string(),
+            })
+            .collect();
+        let house = HousePoolGQL {
+            enabled: *self.state.house_enabled.get(),
+            match_after_micros: self.state.house_match_after_micros.get().to_string(),
+            free_liquidity: self.state.get_balance(&HOUSE_ACCOUNT).await.to_string(),
+            pool_value: pool_value.to_string(),
+            total_shares: total_shares.to_string(),
+            exposure: house_exposure,
+            liquidity_providers,
+        };
+
+        // Convert responsible-gaming limits to GraphQL format, applying changes now due
+        let player_limits: Vec<PlayerLimitsGQL> = self.state.player_limits.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(player, mut limits)| {
+                limits.apply_pending(now);
+                if limits.limits.is_empty() {
+                    return None;
+                }
+                Some(PlayerLimitsGQL {
+                    player: format!("{:?}", player),
+                    limits: limits.limits.iter().map(|l| GamingLimitGQL {
+                        kind: format!("{:?}", l.kind),
+                        period: format!("{:?}", l.period),
+                        amount: l.amount.map(|a| a.to_string()),
+                        used: limits.used(l.kind, l.period, now).to_string(),
+                        pending_amount: l.pending.as_ref().and_then(|p| p.amount).map(|a| a.to_string()),
+                        pending_effective_at: l.pending.as_ref().map(|p| p.effective_at.micros().to_string()),
+                    }).collect(),
+                })
+            })
+            .collect();
+
+        // Queue rate-limit counters
+        let queue_activity: Vec<QueueActivityGQL> = self.state.queue_activity.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(player, activity)| QueueActivityGQL {
+                player: format!("{:?}", player),
+                window_start: activity.window_start.micros().to_string(),
+                window_count: activity.window_count,
+                total: activity.total.to_string(),
+            })
+            .collect();
+
+        // Accounts blocked by the admin
+        let blocked_accounts: Vec<AccountBlockGQL> = self.state.blocked_accounts.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(account, block)| AccountBlockGQL {
+                account: format!("{:?}", account),
+                reason: block.reason,
+                blocked_by: format!("{:?}", block.blocked_by),
+                blocked_at: block.blocked_at.micros().to_string(),
+            })
+            .collect();
+
+        // Pairs of repeat opponents flagged as suspicious
+        let pair_window_micros = *self.state.pair_window_micros.get();
+        let collusion = CollusionGQL {
+            window_micros: pair_window_micros.to_string(),
+            flag_threshold: *self.state.pair_flag_threshold.get(),
+            max_pair_matches: *self.state.pair_match_limit.get(),
+            opposite_ratio_bps: *self.state.pair_opposite_ratio_bps.get(),
+            flagged_pairs: self.state.pair_stats.index_values().await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, stats)| stats.flagged_at.is_some())
+                .map(|((a, b), stats)| PairStatsGQL {
+                    player_a: format!("{:?}", a),
+                    player_b: format!("{:?}", b),
+                    duels: stats.duels.to_string(),
+                    settled: stats.settled.to_string(),
+                    opposite_predictions: stats.opposite_predictions.to_string(),
+                    window_duels: stats.duels_in_window(now, pair_window_micros),
+                    flagged_at: stats.flagged_at.map(|t| t.micros().to_string()),
+                })
+                .collect(),
+        };
+
+        // Accounts still serving a self-exclusion
+        let self_excluded: Vec<SelfExclusionGQL> = self.state.self_excluded_until.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, until)| *until > now)
+            .map(|(player, until)| SelfExclusionGQL {
+                player: format!("{:?}", player),
+                until: until.micros().to_string(),
+            })
+            .collect();
+
+        // Group referred players by referrer
+        let mut referred_by: Vec<(AccountOwner, Vec<String>)> = Vec::new();
+        for (player, referrer) in self.state.referrers.index_values().await.unwrap_or_default() {
+            match referred_by.iter_mut().find(|(r, _)| *r == referrer) {
+                Some((_, referred)) => referred.push(format!("{:?}", player)),
+                None => referred_by.push((referrer, vec![format!("{:?}", player)])),
+            }
+        }
+        let mut referrals = Vec::new();
+        for (referrer, referred) in referred_by {
+            referrals.push(ReferrerGQL {
+                referrer: format!("{:?}", referrer),
+                referred,
+                earnings: self.state.get_referral_earnings(&referrer).await.to_string(),
+            });
+        }
+
+        let rakeback = RakebackGQL {
+            rakeback_bps: *self.state.rakeback_bps.get(),
+            epoch_micros: self.state.rakeback_epoch_micros.get().to_string(),
+            accounts: self.state.rakeback_accounts.index_values().await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(player, account)| RakebackAccountGQL {
+                    player: format!("{:?}", player),
+                    unclaimed_fees: account.unclaimed_fees.to_string(),
+                    claimable: account.accrued.to_string(),
+                    last_claimed_at: account.last_claimed_at.map(|t| t.micros().to_string()),
+                    last_claimed_epoch: account.last_claimed_epoch.map(|epoch| epoch.to_string()),
+                })
+                .collect(),
+            recent_claims: self.state.recent_rakeback_claims.elements().await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|claim| RakebackClaimGQL {
+                    player: format!("{:?}", claim.player),
+                    fees: claim.fees.to_string(),
+                    amount: claim.amount.to_string(),
+                    claimed_at: claim.claimed_at.micros().to_string(),
+                })
+                .collect(),
+        };
+
+        let jackpot = JackpotGQL {
+            pool: self.state.jackpot_pool.get().to_string(),
+            fee_share_bps: *self.state.jackpot_fee_share_bps.get(),
+            win_streak: self.state.jackpot_win_streak.get().to_string(),
+            wins: self.state.jackpot_wins.elements().await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|win| JackpotWinGQL {
+                    player: format!("{:?}", win.player),
+                    duel_id: win.duel_id.to_string(),
+                    win_streak: win.win_streak.to_string(),
+                    amount: win.amount.to_string(),
+                    won_at: win.won_at.micros().to_string(),
+                })
+                .collect(),
+        };
+
         let query_root = QueryRoot {
             chain_id: self.runtime.chain_id().to_string(),
             fee_bps: *self.state.fee_bps.get(),
+            fee_tiers: self.state.fee_tiers.get().iter().map(|tier| FeeTierGQL {
+                min_wagered: tier.min_wagered.to_string(),
+                fee_bps: tier.fee_bps,
+            }).collect(),
             min_bet: self.state.min_bet.get().to_string(),
             max_bet: self.state.max_bet.get().to_string(),
             paused: *self.state.paused.get(),
+            duel_durations: self.state.duel_durations.get().iter().map(|d| d.to_string()).collect(),
+            range_buckets: RangeBucketsGQL::from(self.state.range_buckets.get()),
+            settlement_modes,
+            assets,
+            price_history_len: *self.state.price_history_len.get(),
+            price_history,
+            halted_assets,
             queue_length: self.state.queue.count() as u64,
+            queue_rate_limit_ops: *self.state.queue_rate_limit_ops.get(),
+            queue_rate_limit_window_micros: self.state.queue_rate_limit_window_micros.get().to_string(),
+            queue_activity,
             queue: queue_entries,
+            active_duels,
+            recent_duels,
+            rematch_offers,
+            series,
+            tournaments,
+            pool_interval_micros: self.state.pool_interval_micros.get().to_string(),
+            pool_rounds,
+            recent_pool_rounds,
+            side_pools,
+            recent_side_pools,
+            house,
+            player_limits,
+            self_excluded,
+            blocked_accounts,
+            collusion,
+            referral_fee_share_bps: *self.state.referral_fee_share_bps.get(),
+            referrals,
+            rakeback,
+            jackpot,
             total_duels: *self.state.total_duels.get(),
             total_volume: self.state.total_volume.get().to_string(),
             total_fees: self.state.total_fees.get().to_string(),
@@ -107,19 +484,29 @@ struct DuelInfoGQL {
     player1: String,
     /// Second player address
     player2: String,
-    /// Asset being bet on (BTC or ETH)
+    /// Team one members (player1 first)
+    team_one: Vec<String>,
+    /// Team two members (player2 first)
+    team_two: Vec<String>,
+    /// Asset symbol being bet on
     asset: String,
     /// Bet amount per player
     bet_amount: String,
+    /// Duel duration in microseconds
+    duration_micros: String,
+    /// Kind of prediction (Direction, Range, ClosestPrice or Relative)
+    mode: String,
+    /// Asset compared against (relative duels)
+    versus_asset: Option<String>,
     /// Current duel status
     status: String,
     /// When duel was created
     created_at: String,
     /// Winner address (if resolved)
     winner: Option<String>,
-    /// Player 1's prediction (Up or Down)
+    /// Team one's prediction
     p1_prediction: Option<String>,
-    /// Player 2's prediction (Up or Down)
+    /// Team two's prediction
     p2_prediction: Option<String>,
     /// Start price when predictions were locked
     start_price: Option<String>,
@@ -127,6 +514,108 @@ struct DuelInfoGQL {
     end_price: Option<String>,
     /// When duel started (predictions locked)
     started_at: Option<String>,
+    /// Duel this one is a rematch of
+    previous_duel: Option<String>,
+    /// Series this duel belongs to
+    series_id: Option<String>,
+    /// Settlement mode (Spot or Twap)
+    settlement: String,
+    /// Time-weighted start price (TWAP settlement)
+    start_twap: Option<String>,
+    /// Time-weighted end price (TWAP settlement)
+    end_twap: Option<String>,
+    /// Start price of the versus asset (relative duels)
+    versus_start_price: Option<String>,
+    /// End price of the versus asset (relative duels)
+    versus_end_price: Option<String>,
+    /// Time-weighted start price of the versus asset (relative duels, TWAP settlement)
+    versus_start_twap: Option<String>,
+    /// Time-weighted end price of the versus asset (relative duels, TWAP settlement)
+    versus_end_twap: Option<String>,
+    /// Effective fee charged at settlement across all participants
+    total_fee: String,
+    /// Bucket edge and payouts fixed at creation (range duels)
+    range_buckets: Option<RangeBucketsGQL>,
+}
+
+impl From<&DuelInfo> for DuelInfoGQL {
+    fn from(duel: &DuelInfo) -> Self {
+        let team = |team: Team| -> Vec<String> {
+            duel.participants.iter()
+                .filter(|p| p.team == team)
+                .map(|p| format!("{:?}", p.player))
+                .collect()
+        };
+        Self {
+            id: duel.id.to_string(),
+            player1: format!("{:?}", duel.player1),
+            player2: format!("{:?}", duel.player2),
+            team_one: team(Team::One),
+            team_two: team(Team::Two),
+            asset: duel.asset.to_string(),
+            bet_amount: duel.bet_amount.to_string(),
+            duration_micros: duel.duration_micros.to_string(),
+            mode: format!("{:?}", duel.mode),
+            versus_asset: duel.versus_asset.as_ref().map(|a| a.to_string()),
+            status: format!("{:?}", duel.status),
+            created_at: duel.created_at.micros().to_string(),
+            winner: duel.winner.map(|w| format!("{:?}", w)),
+            p1_prediction: duel.p1_prediction.map(|p| format!("{:?}", p)),
+            p2_prediction: duel.p2_prediction.map(|p| format!("{:?}", p)),
+            start_price: duel.start_price.map(|p| p.to_string()),
+            end_price: duel.end_price.map(|p| p.to_string()),
+            started_at: duel.started_at.map(|t| t.micros().to_string()),
+            previous_duel: duel.previous_duel.map(|id| id.to_string()),
+            series_id: duel.series_id.map(|id| id.to_string()),
+            settlement: format!("{:?}", duel.settlement),
+            start_twap: duel.start_twap.map(|p| p.to_string()),
+            end_twap: duel.end_twap.map(|p| p.to_string()),
+            versus_start_price: duel.versus_start_price.map(|p| p.to_string()),
+            versus_end_price: duel.versus_end_price.map(|p| p.to_string()),
+            versus_start_twap: duel.versus_start_twap.map(|p| p.to_string()),
+            versus_end_twap: duel.versus_end_twap.map(|p| p.to_string()),
+            total_fee: duel.total_fee().to_string(),
+            range_buckets: duel.range_buckets.as_ref().map(RangeBucketsGQL::from),
+        }
+    }
+}
+
+/// GraphQL representation of range duel buckets and payouts for client queries
+#[derive(SimpleObject)]
+struct RangeBucketsGQL {
+    /// Move separating small from large buckets in basis points
+    edge_bps: String,
+    /// Share of the losing stakes the winner takes in basis points, by buckets missed
+    payout_bps: Vec<u16>,
+}
+
+impl From<&RangeBuckets> for RangeBucketsGQL {
+    fn from(buckets: &RangeBuckets) -> Self {
+        Self {
+            edge_bps: buckets.edge_bps.to_string(),
+            payout_bps: buckets.payout_bps.clone(),
+        }
+    }
+}
+
+/// GraphQL representation of how new duels of a mode are settled for client queries
+#[derive(SimpleObject)]
+struct SettlementModeGQL {
+    /// Duel mode (Direction, Range, ClosestPrice or Relative)
+    duel_mode: String,
+    /// Settlement mode (Spot or Twap)
+    settlement: String,
+    /// TWAP averaging window in microseconds (TWAP settlement)
+    twap_window_micros: Option<String>,
+}
+
+/// GraphQL representation of a volume-based fee tier for client queries
+#[derive(SimpleObject)]
+struct FeeTierGQL {
+    /// Lifetime amount wagered to qualify (in micro-units)
+    min_wagered: String,
+    /// Fee in basis points
+    fee_bps: u16,
 }
 
 /// GraphQL representation of player statistics for client queries
@@ -146,6 +635,18 @@ struct PlayerStatsGQL {
     best_streak: String,
     /// Win rate percentage (0-100)
     win_rate: String,
+    /// Total fees paid on settled duels (in micro-units)
+    fees_paid: String,
+    /// Consecutive staked 1v1 duel wins (jackpot streak)
+    duel_win_streak: String,
+    /// Total amount staked on side bets (in micro-units)
+    side_bets_wagered: String,
+    /// Side bets won
+    side_bet_wins: String,
+    /// Side bets lost
+    side_bet_losses: String,
+    /// Total paid out on winning side bets (in micro-units)
+    side_bets_won: String,
 }
 
 impl From<&PlayerStats> for PlayerStatsGQL {
@@ -158,23 +659,307 @@ impl From<&PlayerStats> for PlayerStatsGQL {
             win_streak: stats.win_streak.to_string(),
             best_streak: stats.best_streak.to_string(),
             win_rate: stats.win_rate().to_string(),
+            fees_paid: stats.fees_paid.to_string(),
+            duel_win_streak: stats.duel_win_streak.to_string(),
+            side_bets_wagered: stats.side_bets_wagered.to_string(),
+            side_bet_wins: stats.side_bet_wins.to_string(),
+            side_bet_losses: stats.side_bet_losses.to_string(),
+            side_bets_won: stats.side_bets_won.to_string(),
         }
     }
 }
 
+/// GraphQL representation of an asset registry entry for client queries
+#[derive(SimpleObject)]
+struct AssetConfigGQL {
+    /// Asset symbol
+    symbol: String,
+    /// Number of decimals to show in the UI
+    display_decimals: u8,
+    /// Currency prices are quoted in
+    quote_currency: String,
+    /// Number of decimals prices are stored with
+    price_decimals: u8,
+    /// Whether new bets on the asset are accepted
+    enabled: bool,
+    /// Minimum bet amount (in micro-units)
+    min_bet: String,
+    /// Maximum bet amount (in micro-units)
+    max_bet: String,
+    /// Accounts allowed to report prices (anyone if empty)
+    oracle_sources: Vec<String>,
+    /// Largest move from the previous quote before the market halts
+    max_deviation_bps: Option<String>,
+    /// Longest gap between quotes before the market halts
+    max_staleness_micros: Option<String>,
+    /// Most the house may have staked on the asset at once
+    house_exposure_limit: String,
+}
+
+/// Name of a settlement mode for client queries
+fn settlement_name(mode: SettlementMode) -> String {
+    match mode {
+        SettlementMode::Spot => "Spot".to_string(),
+        SettlementMode::Twap { .. } => "Twap".to_string(),
+    }
+}
+
+/// TWAP averaging window of a settlement mode, if any
+fn twap_window(mode: SettlementMode) -> Option<String> {
+    match mode {
+        SettlementMode::Spot => None,
+        SettlementMode::Twap { window_micros } => Some(window_micros.to_string()),
+    }
+}
+
+/// GraphQL representation of a player's queue joins and leaves for client queries
+#[derive(SimpleObject)]
+struct QueueActivityGQL {
+    /// Player address
+    player: String,
+    /// When the current rate-limit window started
+    window_start: String,
+    /// Joins and leaves in the current window
+    window_count: u32,
+    /// Joins and leaves ever
+    total: String,
+}
+
 /// GraphQL representation of a queue entry for client queries
 #[derive(SimpleObject)]
 struct QueueEntryGQL {
     /// Player address
     player: String,
-    /// Asset to bet on (BTC or ETH)
+    /// Asset symbol to bet on
     asset: String,
     /// Bet amount (in micro-units)
     bet_amount: String,
+    /// Chosen duel duration in microseconds
+    duration_micros: String,
+    /// Players per team (1 for a solo duel)
+    team_size: u8,
+    /// Kind of prediction (Direction, Range, ClosestPrice or Relative)
+    mode: String,
+    /// Asset compared against (relative duels)
+    versus_asset: Option<String>,
     /// When the player joined the queue
     joined_at: String,
 }
 
+/// GraphQL representation of a pending rematch offer for client queries
+#[derive(SimpleObject)]
+struct RematchOfferGQL {
+    /// Resolved duel being rematched
+    duel_id: String,
+    /// Player who offered the rematch
+    offered_by: String,
+    /// Player who may accept the rematch
+    opponent: String,
+    /// Asset symbol to bet on
+    asset: String,
+    /// Bet amount per player (in micro-units)
+    bet_amount: String,
+    /// Duel duration in microseconds
+    duration_micros: String,
+    /// Kind of prediction (Direction, Range, ClosestPrice or Relative)
+    mode: String,
+    /// Asset compared against (relative duels)
+    versus_asset: Option<String>,
+    /// When the offer was made
+    offered_at: String,
+}
+
+/// GraphQL representation of a best-of-N series for client queries
+#[derive(SimpleObject)]
+struct SeriesInfoGQL {
+    /// Unique series identifier
+    id: String,
+    /// Player who issued the challenge
+    player1: String,
+    /// Challenged player
+    player2: String,
+    /// Asset symbol being bet on
+    asset: String,
+    /// Series stake per player (in micro-units)
+    stake: String,
+    /// Maximum number of duels in the series
+    best_of: u32,
+    /// Duration of each duel in microseconds
+    duration_micros: String,
+    /// Duel wins required to take the series
+    wins_needed: u32,
+    /// Duels played so far, in order
+    duel_ids: Vec<String>,
+    /// Duels won by player 1
+    p1_wins: u32,
+    /// Duels won by player 2
+    p2_wins: u32,
+    /// Current series status
+    status: String,
+    /// Series winner address (if completed)
+    winner: Option<String>,
+    /// When the challenge was issued
+    created_at: String,
+}
+
+/// GraphQL representation of a tournament bracket match for client queries
+#[derive(SimpleObject)]
+struct BracketMatchGQL {
+    /// Round number (0 = first round)
+    round: u32,
+    /// Position within the round
+    slot: u32,
+    /// First player address
+    player1: String,
+    /// Second player address
+    player2: String,
+    /// Duel deciding this match (none if decided by forfeit)
+    duel_id: Option<String>,
+    /// Winner address (once resolved)
+    winner: Option<String>,
+}
+
+/// GraphQL representation of a tournament for client queries
+#[derive(SimpleObject)]
+struct TournamentInfoGQL {
+    /// Unique tournament identifier
+    id: String,
+    /// Asset symbol being bet on
+    asset: String,
+    /// Entry fee per player (in micro-units)
+    entry_fee: String,
+    /// Duration of each duel in microseconds
+    duration_micros: String,
+    /// Number of players
+    size: u32,
+    /// Prize pool share per placement tier in basis points
+    prize_split: Vec<u16>,
+    /// Registered player addresses, in seeding order
+    players: Vec<String>,
+    /// Bracket matches across all rounds
+    bracket: Vec<BracketMatchGQL>,
+    /// Round currently being played
+    current_round: u32,
+    /// Total number of rounds
+    rounds: u32,
+    /// Current tournament status
+    status: String,
+    /// Collected entry fees (in micro-units)
+    prize_pool: String,
+    /// Champion address (if completed)
+    champion: Option<String>,
+    /// When the tournament was created
+    created_at: String,
+}
+
+/// GraphQL representation of a stake in a pool round for client queries
+#[derive(SimpleObject)]
+struct PoolEntryGQL {
+    /// Player address
+    player: String,
+    /// Predicted direction (Up or Down)
+    direction: String,
+    /// Staked amount (in micro-units)
+    amount: String,
+}
+
+/// GraphQL representation of a parimutuel pool round for client queries
+#[derive(SimpleObject)]
+struct PoolRoundGQL {
+    /// Unique round identifier
+    id: String,
+    /// Asset symbol being bet on
+    asset: String,
+    /// When staking opened
+    opens_at: String,
+    /// When staking closes and the start price is locked
+    locks_at: String,
+    /// When the round can be resolved
+    resolves_at: String,
+    /// Stakes placed in the round
+    entries: Vec<PoolEntryGQL>,
+    /// Total staked on Up (in micro-units)
+    total_up: String,
+    /// Total staked on Down (in micro-units)
+    total_down: String,
+    /// Start price when the round was locked
+    start_price: Option<String>,
+    /// End price when the round was resolved
+    end_price: Option<String>,
+    /// Winning direction (when resolved)
+    outcome: Option<String>,
+    /// Current round status
+    status: String,
+}
+
+impl From<&PoolRound> for PoolRoundGQL {
+    fn from(round: &PoolRound) -> Self {
+        Self {
+            id: round.id.to_string(),
+            asset: round.asset.to_string(),
+            opens_at: round.opens_at.micros().to_string(),
+            locks_at: round.locks_at.micros().to_string(),
+            resolves_at: round.resolves_at.micros().to_string(),
+            entries: round.entries.iter().map(|e| PoolEntryGQL {
+                player: format!("{:?}", e.player),
+                direction: format!("{:?}", e.direction),
+                amount: e.amount.to_string(),
+            }).collect(),
+            total_up: round.total_for(Direction::Up).to_string(),
+            total_down: round.total_for(Direction::Down).to_string(),
+            start_price: round.start_price.map(|p| p.to_string()),
+            end_price: round.end_price.map(|p| p.to_string()),
+            outcome: round.outcome.map(|d| format!("{:?}", d)),
+            status: format!("{:?}", round.status),
+        }
+    }
+}
+
+/// GraphQL representation of a spectator bet for client queries
+#[derive(SimpleObject)]
+struct SideBetGQL {
+    /// Spectator address
+    bettor: String,
+    /// Team backed (One or Two)
+    team: String,
+    /// Staked amount (in micro-units)
+    amount: String,
+}
+
+/// GraphQL representation of a duel's spectator side pool for client queries
+#[derive(SimpleObject)]
+struct SidePoolGQL {
+    /// Duel being bet on
+    duel_id: String,
+    /// Bets placed
+    bets: Vec<SideBetGQL>,
+    /// Total staked on team One (in micro-units)
+    total_team_one: String,
+    /// Total staked on team Two (in micro-units)
+    total_team_two: String,
+    /// Winning team (when settled)
+    winning_team: Option<String>,
+    /// Current pool status
+    status: String,
+}
+
+impl From<&SidePool> for SidePoolGQL {
+    fn from(pool: &SidePool) -> Self {
+        Self {
+            duel_id: pool.duel_id.to_string(),
+            bets: pool.bets.iter().map(|b| SideBetGQL {
+                bettor: format!("{:?}", b.bettor),
+                team: format!("{:?}", b.team),
+                amount: b.amount.to_string(),
+            }).collect(),
+            total_team_one: pool.total_for(Team::One).to_string(),
+            total_team_two: pool.total_for(Team::Two).to_string(),
+            winning_team: pool.winning_team.map(|t| format!("{:?}", t)),
+            status: format!("{:?}", pool.status),
+        }
+    }
+}
+
 /// GraphQL representation of a leaderboard entry for client queries
 #[derive(SimpleObject)]
 struct LeaderboardEntryGQL {
@@ -201,14 +986,241 @@ struct LeaderboardEntryGQL {
 /// GraphQL representation of price data for client queries
 #[derive(SimpleObject)]
 struct PriceDataGQL {
-    /// Asset type (BTC or ETH)
+    /// Asset symbol
     asset: String,
-    /// Price in micro-USD (1 USD = 1_000_000)
+    /// Price as a decimal string in the quote currency
     price: String,
+    /// Currency the price is quoted in
+    quote_currency: String,
     /// When price was fetched
     timestamp: String,
 }
 
+/// GraphQL representation of an asset's recent quotes for client queries
+#[derive(SimpleObject)]
+struct PriceHistoryGQL {
+    /// Asset symbol
+    asset: String,
+    /// Recent quotes, oldest first
+    quotes: Vec<PriceDataGQL>,
+}
+
+/// GraphQL representation of a tripped circuit breaker for client queries
+#[derive(SimpleObject)]
+struct AssetHaltGQL {
+    /// Halted asset symbol
+    asset: String,
+    /// Why the circuit breaker tripped
+    reason: String,
+    /// Quote that tripped the breaker
+    price: String,
+    /// When the asset was halted
+    halted_at: String,
+}
+
+/// GraphQL representation of the house stake on an asset for client queries
+#[derive(SimpleObject)]
+struct HouseExposureGQL {
+    /// Asset symbol
+    asset: String,
+    /// House stake locked in active duels
+    amount: String,
+    /// Exposure limit for the asset
+    limit: String,
+}
+
+/// GraphQL representation of a liquidity provider's position for client queries
+#[derive(SimpleObject)]
+struct LpPositionGQL {
+    /// Provider address
+    provider: String,
+    /// LP shares held
+    shares: String,
+    /// Current value of the shares
+    value: String,
+}
+
+/// GraphQL representation of the house liquidity pool for client queries
+#[derive(SimpleObject)]
+struct HousePoolGQL {
+    /// Whether the house matches players left waiting
+    enabled: bool,
+    /// How long a player waits before the house takes the other side
+    match_after_micros: String,
+    /// Liquidity not locked in duels
+    free_liquidity: String,
+    /// Free liquidity plus stakes locked in active duels
+    pool_value: String,
+    /// Total LP shares outstanding
+    total_shares: String,
+    /// House stake by asset
+    exposure: Vec<HouseExposureGQL>,
+    /// Liquidity provider positions
+    liquidity_providers: Vec<LpPositionGQL>,
+}
+
+/// GraphQL representation of a responsible-gaming limit for client queries
+#[derive(SimpleObject)]
+struct GamingLimitGQL {
+    /// What the limit caps (Deposit, Loss or Wager)
+    kind: String,
+    /// Rolling period (Day or Week)
+    period: String,
+    /// Limit in effect (unlimited if null)
+    amount: Option<String>,
+    /// Amount counted towards the limit in the current period
+    used: String,
+    /// Pending new limit (a removal if null while pending_effective_at is set)
+    pending_amount: Option<String>,
+    /// When the pending change takes effect
+    pending_effective_at: Option<String>,
+}
+
+/// GraphQL representation of a player's responsible-gaming limits for client queries
+#[derive(SimpleObject)]
+struct PlayerLimitsGQL {
+    /// Player address
+    player: String,
+    /// Configured limits
+    limits: Vec<GamingLimitGQL>,
+}
+
+/// GraphQL representation of a pair of repeat opponents for client queries
+#[derive(SimpleObject)]
+struct PairStatsGQL {
+    /// First player (lower address)
+    player_a: String,
+    /// Second player
+    player_b: String,
+    /// Duels played against each other
+    duels: String,
+    /// Duels between them that settled
+    settled: String,
+    /// Settled duels where they made opposite predictions
+    opposite_predictions: String,
+    /// Duels in the current window
+    window_duels: u32,
+    /// When the pair was flagged as suspicious
+    flagged_at: Option<String>,
+}
+
+/// GraphQL representation of collusion detection for client queries
+#[derive(SimpleObject)]
+struct CollusionGQL {
+    /// Window for counting duels between the same pair in microseconds
+    window_micros: String,
+    /// Duels between the same pair within a window that flag it
+    flag_threshold: u32,
+    /// Most duels matched between the same pair per window
+    max_pair_matches: Option<u32>,
+    /// Share of settled duels with opposite predictions that flags a pair in basis points
+    opposite_ratio_bps: Option<u16>,
+    /// Pairs flagged as suspicious
+    flagged_pairs: Vec<PairStatsGQL>,
+}
+
+/// GraphQL representation of a blocked account for client queries
+#[derive(SimpleObject)]
+struct AccountBlockGQL {
+    /// Blocked account
+    account: String,
+    /// Why the account was blocked
+    reason: String,
+    /// Admin who blocked it
+    blocked_by: String,
+    /// When it was blocked
+    blocked_at: String,
+}
+
+/// GraphQL representation of a self-excluded account for client queries
+#[derive(SimpleObject)]
+struct SelfExclusionGQL {
+    /// Player address
+    player: String,
+    /// When the exclusion ends
+    until: String,
+}
+
+/// GraphQL representation of a referrer's referrals for client queries
+#[derive(SimpleObject)]
+struct ReferrerGQL {
+    /// Referrer address
+    referrer: String,
+    /// Players who registered this referrer
+    referred: Vec<String>,
+    /// Total referral earnings (in micro-units)
+    earnings: String,
+}
+
+/// GraphQL representation of a player's unclaimed rakeback for client queries
+#[derive(SimpleObject)]
+struct RakebackAccountGQL {
+    /// Player address
+    player: String,
+    /// Fees paid since the last claim (in micro-units)
+    unclaimed_fees: String,
+    /// Rakeback currently claimable (in micro-units)
+    claimable: String,
+    /// When the player last claimed
+    last_claimed_at: Option<String>,
+    /// Epoch of the last claim (system time divided by the epoch length)
+    last_claimed_epoch: Option<String>,
+}
+
+/// GraphQL representation of a rakeback payout for client queries
+#[derive(SimpleObject)]
+struct RakebackClaimGQL {
+    /// Claiming player
+    player: String,
+    /// Fees the rakeback was paid on (in micro-units)
+    fees: String,
+    /// Amount paid out (in micro-units)
+    amount: String,
+    /// When the claim was made
+    claimed_at: String,
+}
+
+/// GraphQL representation of rakeback settings and balances for client queries
+#[derive(SimpleObject)]
+struct RakebackGQL {
+    /// Share of fees paid returned in basis points
+    rakeback_bps: u16,
+    /// Epoch length in microseconds (minimum time between claims)
+    epoch_micros: String,
+    /// Players with fees awaiting rakeback
+    accounts: Vec<RakebackAccountGQL>,
+    /// Rakeback claims (recent history)
+    recent_claims: Vec<RakebackClaimGQL>,
+}
+
+/// GraphQL representation of a jackpot payout for client queries
+#[derive(SimpleObject)]
+struct JackpotWinGQL {
+    /// Winning player
+    player: String,
+    /// Duel that triggered the jackpot
+    duel_id: String,
+    /// Win streak reached
+    win_streak: String,
+    /// Amount paid out (in micro-units)
+    amount: String,
+    /// When the jackpot was won
+    won_at: String,
+}
+
+/// GraphQL representation of the progressive jackpot for client queries
+#[derive(SimpleObject)]
+struct JackpotGQL {
+    /// Current jackpot (in micro-units)
+    pool: String,
+    /// Share of each duel fee fed into the jackpot in basis points
+    fee_share_bps: u16,
+    /// Win streak that wins the jackpot
+    win_streak: String,
+    /// Every jackpot paid out, oldest first
+    wins: Vec<JackpotWinGQL>,
+}
+
 /// GraphQL representation of platform info for client queries
 #[derive(SimpleObject)]
 struct PlatformInfoGQL {
@@ -239,16 +1251,76 @@ struct QueryRoot {
     chain_id: String,
     /// Platform fee in basis points
     fee_bps: u16,
+    /// Discounted fee tiers by lifetime amount wagered
+    fee_tiers: Vec<FeeTierGQL>,
     /// Minimum bet amount
     min_bet: String,
     /// Maximum bet amount
     max_bet: String,
     /// Is platform paused
     paused: bool,
+    /// Duel durations players may choose from, in microseconds
+    duel_durations: Vec<String>,
+    /// Bucket edge and tiered payouts of new range duels
+    range_buckets: RangeBucketsGQL,
+    /// Settlement of new duels by mode (modes not listed settle on spot prices)
+    settlement_modes: Vec<SettlementModeGQL>,
+    /// Asset registry
+    assets: Vec<AssetConfigGQL>,
+    /// Number of quotes kept per asset
+    price_history_len: u32,
+    /// Recent quotes per asset, oldest first
+    price_history: Vec<PriceHistoryGQL>,
+    /// Assets halted by a circuit breaker
+    halted_assets: Vec<AssetHaltGQL>,
     /// Number of players in queue
     queue_length: u64,
+    /// Queue joins and leaves allowed per player per window
+    queue_rate_limit_ops: u32,
+    /// Queue rate-limit window in microseconds
+    queue_rate_limit_window_micros: String,
+    /// Queue joins and leaves by player
+    queue_activity: Vec<QueueActivityGQL>,
     /// Matchmaking queue entries
     queue: Vec<QueueEntryGQL>,
+    /// Duels waiting for predictions, running or held for review
+    active_duels: Vec<DuelInfoGQL>,
+    /// Completed duels (recent history)
+    recent_duels: Vec<DuelInfoGQL>,
+    /// Pending rematch offers
+    rematch_offers: Vec<RematchOfferGQL>,
+    /// Best-of-N series and their progress
+    series: Vec<SeriesInfoGQL>,
+    /// Tournaments with full bracket state
+    tournaments: Vec<TournamentInfoGQL>,
+    /// Pool round staking interval in microseconds
+    pool_interval_micros: String,
+    /// Open and locked pool rounds
+    pool_rounds: Vec<PoolRoundGQL>,
+    /// Settled pool rounds (recent history)
+    recent_pool_rounds: Vec<PoolRoundGQL>,
+    /// Spectator side pools of duels not yet resolved
+    side_pools: Vec<SidePoolGQL>,
+    /// Settled side pools (recent history)
+    recent_side_pools: Vec<SidePoolGQL>,
+    /// House liquidity pool
+    house: HousePoolGQL,
+    /// Players' responsible-gaming limits
+    player_limits: Vec<PlayerLimitsGQL>,
+    /// Accounts currently self-excluded
+    self_excluded: Vec<SelfExclusionGQL>,
+    /// Accounts blocked by the admin
+    blocked_accounts: Vec<AccountBlockGQL>,
+    /// Collusion detection settings and flagged pairs
+    collusion: CollusionGQL,
+    /// Share of a referred player's fees credited to their referrer in basis points
+    referral_fee_share_bps: u16,
+    /// Referrers, their referred players and earnings
+    referrals: Vec<ReferrerGQL>,
+    /// Rakeback settings, balances and claims
+    rakeback: RakebackGQL,
+    /// Progressive jackpot and its payouts
+    jackpot: JackpotGQL,
     /// Total duels completed
     total_duels: u64,
     /// Total volume traded
@@ -270,18 +1342,39 @@ impl MutationRoot {
     /// Join matchmaking queue
     ///
     /// Schedules a JoinQueue operation to be executed by the contract.
-    async fn join_queue(&self, asset: String, bet_amount: String) -> Vec<u8> {
-        let asset_enum = match asset.to_uppercase().as_str() {
-            "BTC" => Asset::BTC,
-            "ETH" => Asset::ETH,
-            _ => panic!("Unsupported asset"),
-        };
+    #[allow(clippy::too_many_arguments)]
+    async fn join_queue(
+        &self,
+        asset: String,
+        bet_amount: String,
+        duration_micros: Option<String>,
+        team_size: Option<u8>,
+        mode: Option<String>,
+        versus_asset: Option<String>,
+    ) -> Vec<u8> {
+        // Registry membership is checked by the contract
+        let asset_enum = Asset::new(&asset);
+        let versus_enum = versus_asset.as_deref().map(Asset::new);
 
         let amount: u128 = bet_amount.parse().expect("Invalid bet amount");
+        let duration: u64 = duration_micros
+            .map(|d| d.parse().expect("Invalid duration"))
+            .unwrap_or(DUEL_DURATION_MICROS);
+        let duel_mode = match mode.as_deref().map(str::to_uppercase).as_deref() {
+            None | Some("DIRECTION") => DuelMode::Direction,
+            Some("RANGE") => DuelMode::Range,
+            Some("CLOSEST_PRICE") => DuelMode::ClosestPrice,
+            Some("RELATIVE") => DuelMode::Relative,
+            _ => panic!("Mode must be DIRECTION, RANGE, CLOSEST_PRICE or RELATIVE"),
+        };
 
         let operation = Operation::JoinQueue {
             asset: asset_enum,
             bet_amount: Amount::from_attos(amount),
+            duration_micros: duration,
+            team_size: team_size.unwrap_or(1),
+            mode: duel_mode,
+            versus_asset: versus_enum,
         };
 
         self.runtime.schedule_operation(&operation);
@@ -298,21 +1391,238 @@ impl MutationRoot {
 
     /// Submit prediction for a duel
     ///
+    /// Pass `direction` (UP/DOWN) for direction duels, `bucket`
+    /// (DOWN_LARGE/DOWN_SMALL/UP_SMALL/UP_LARGE) for range duels or
+    /// `outperformer` (BASE/VERSUS) for relative duels.
     /// Schedules a SubmitPrediction operation to be executed by the contract.
-    async fn submit_prediction(&self, duel_id: String, direction: String) -> Vec<u8> {
+    async fn submit_prediction(
+        &self,
+        duel_id: String,
+        direction: Option<String>,
+        bucket: Option<String>,
+        outperformer: Option<String>,
+    ) -> Vec<u8> {
+        let id: u64 = duel_id.parse().expect("Invalid duel ID");
+        let prediction = match (direction, bucket, outperformer) {
+            (Some(direction), None, None) => Prediction::Direction(match direction.to_uppercase().as_str() {
+                "UP" => Direction::Up,
+                "DOWN" => Direction::Down,
+                _ => panic!("Direction must be UP or DOWN"),
+            }),
+            (None, Some(bucket), None) => Prediction::Range(match bucket.to_uppercase().as_str() {
+                "DOWN_LARGE" => PriceBucket::DownLarge,
+                "DOWN_SMALL" => PriceBucket::DownSmall,
+                "UP_SMALL" => PriceBucket::UpSmall,
+                "UP_LARGE" => PriceBucket::UpLarge,
+                _ => panic!("Bucket must be DOWN_LARGE, DOWN_SMALL, UP_SMALL or UP_LARGE"),
+            }),
+            (None, None, Some(leg)) => Prediction::Outperformer(match leg.to_uppercase().as_str() {
+                "BASE" => Leg::Base,
+                "VERSUS" => Leg::Versus,
+                _ => panic!("Outperformer must be BASE or VERSUS"),
+            }),
+            _ => panic!("Provide exactly one of direction, bucket or outperformer"),
+        };
+
+        let operation = Operation::SubmitPrediction {
+            duel_id: DuelId(id),
+            prediction,
+        };
+
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Commit to a hidden target price in a closest-price duel
+    ///
+    /// Only the commitment hash leaves this node; keep the price and salt to reveal later.
+    /// Schedules a CommitGuess operation to be executed by the contract.
+    async fn commit_guess(&self, duel_id: String, price: String, salt: String) -> Vec<u8> {
+        let id: u64 = duel_id.parse().expect("Invalid duel ID");
+        let target: Price = price.parse().expect("Invalid price");
+
+        let reveal = GuessReveal { duel_id: DuelId(id), target, salt };
+        let operation = Operation::CommitGuess {
+            duel_id: DuelId(id),
+            commitment: reveal.commitment(),
+        };
+
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Reveal a committed target price
+    ///
+    /// Schedules a RevealGuess operation to be executed by the contract.
+    async fn reveal_guess(&self, duel_id: String, price: String, salt: String) -> Vec<u8> {
         let id: u64 = duel_id.parse().expect("Invalid duel ID");
+        let target: Price = price.parse().expect("Invalid price");
+
+        let operation = Operation::RevealGuess {
+            duel_id: DuelId(id),
+            target,
+            salt,
+        };
+
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Offer a rematch of a resolved duel
+    ///
+    /// Schedules an OfferRematch operation to be executed by the contract.
+    async fn offer_rematch(&self, duel_id: String, double_stake: Option<bool>) -> Vec<u8> {
+        let id: u64 = duel_id.parse().expect("Invalid duel ID");
+
+        self.runtime.schedule_operation(&Operation::OfferRematch {
+            duel_id: DuelId(id),
+            double_stake: double_stake.unwrap_or(false),
+        });
+        vec![]
+    }
+
+    /// Accept a rematch offer
+    ///
+    /// Schedules an AcceptRematch operation to be executed by the contract.
+    async fn accept_rematch(&self, duel_id: String) -> Vec<u8> {
+        let id: u64 = duel_id.parse().expect("Invalid duel ID");
+
+        self.runtime.schedule_operation(&Operation::AcceptRematch {
+            duel_id: DuelId(id),
+        });
+        vec![]
+    }
+
+    /// Back one team of a duel that has not started yet
+    ///
+    /// Schedules a PlaceSideBet operation to be executed by the contract.
+    async fn place_side_bet(&self, duel_id: String, team: String, amount: String) -> Vec<u8> {
+        let id: u64 = duel_id.parse().expect("Invalid duel ID");
+        let team = match team.to_uppercase().as_str() {
+            "ONE" => Team::One,
+            "TWO" => Team::Two,
+            _ => panic!("Team must be ONE or TWO"),
+        };
+
+        let amt: u128 = amount.parse().expect("Invalid amount");
+
+        self.runtime.schedule_operation(&Operation::PlaceSideBet {
+            duel_id: DuelId(id),
+            team,
+            amount: Amount::from_attos(amt),
+        });
+        vec![]
+    }
+
+    /// Challenge a player to a best-of-N series
+    ///
+    /// Schedules a ChallengeSeries operation to be executed by the contract.
+    async fn challenge_series(
+        &self,
+        opponent: String,
+        asset: String,
+        stake: String,
+        best_of: u32,
+        duration_micros: Option<String>,
+    ) -> Vec<u8> {
+        let opponent: AccountOwner = opponent.parse().expect("Invalid opponent address");
+        // Registry membership is checked by the contract
+        let asset_enum = Asset::new(&asset);
+
+        let amount: u128 = stake.parse().expect("Invalid stake");
+        let duration: u64 = duration_micros
+            .map(|d| d.parse().expect("Invalid duration"))
+            .unwrap_or(DUEL_DURATION_MICROS);
+
+        self.runtime.schedule_operation(&Operation::ChallengeSeries {
+            opponent,
+            asset: asset_enum,
+            stake: Amount::from_attos(amount),
+            best_of,
+            duration_micros: duration,
+        });
+        vec![]
+    }
+
+    /// Accept a series challenge
+    ///
+    /// Schedules an AcceptSeries operation to be executed by the contract.
+    async fn accept_series(&self, series_id: String) -> Vec<u8> {
+        let id: u64 = series_id.parse().expect("Invalid series ID");
+
+        self.runtime.schedule_operation(&Operation::AcceptSeries {
+            series_id: SeriesId(id),
+        });
+        vec![]
+    }
+
+    /// Withdraw or decline a pending series challenge
+    ///
+    /// Schedules a CancelSeries operation to be executed by the contract.
+    async fn cancel_series(&self, series_id: String) -> Vec<u8> {
+        let id: u64 = series_id.parse().expect("Invalid series ID");
+
+        self.runtime.schedule_operation(&Operation::CancelSeries {
+            series_id: SeriesId(id),
+        });
+        vec![]
+    }
+
+    /// Register for a tournament
+    ///
+    /// Schedules a RegisterTournament operation to be executed by the contract.
+    async fn register_tournament(&self, tournament_id: String) -> Vec<u8> {
+        let id: u64 = tournament_id.parse().expect("Invalid tournament ID");
+
+        self.runtime.schedule_operation(&Operation::RegisterTournament {
+            tournament_id: TournamentId(id),
+        });
+        vec![]
+    }
+
+    /// Stake in the current pool round for an asset
+    ///
+    /// Schedules a JoinPool operation to be executed by the contract.
+    async fn join_pool(&self, asset: String, direction: String, amount: String) -> Vec<u8> {
+        // Registry membership is checked by the contract
+        let asset_enum = Asset::new(&asset);
         let dir = match direction.to_uppercase().as_str() {
             "UP" => Direction::Up,
             "DOWN" => Direction::Down,
             _ => panic!("Direction must be UP or DOWN"),
         };
 
-        let operation = Operation::SubmitPrediction {
-            duel_id: DuelId(id),
+        let amt: u128 = amount.parse().expect("Invalid amount");
+
+        self.runtime.schedule_operation(&Operation::JoinPool {
+            asset: asset_enum,
             direction: dir,
-        };
+            amount: Amount::from_attos(amt),
+        });
+        vec![]
+    }
 
-        self.runtime.schedule_operation(&operation);
+    /// Lock the start price of a pool round
+    ///
+    /// Schedules a LockPoolRound operation to be executed by the contract.
+    async fn lock_pool_round(&self, round_id: String) -> Vec<u8> {
+        let id: u64 = round_id.parse().expect("Invalid round ID");
+
+        self.runtime.schedule_operation(&Operation::LockPoolRound {
+            round_id: RoundId(id),
+        });
+        vec![]
+    }
+
+    /// Resolve a pool round
+    ///
+    /// Schedules a ResolvePoolRound operation to be executed by the contract.
+    async fn resolve_pool_round(&self, round_id: String) -> Vec<u8> {
+        let id: u64 = round_id.parse().expect("Invalid round ID");
+
+        self.runtime.schedule_operation(&Operation::ResolvePoolRound {
+            round_id: RoundId(id),
+        });
         vec![]
     }
 
@@ -340,17 +1650,98 @@ impl MutationRoot {
         vec![]
     }
 
+    /// Lock yourself out of betting until a time (in microseconds)
+    ///
+    /// Schedules a SelfExclude operation to be executed by the contract.
+    async fn self_exclude(&self, until: String) -> Vec<u8> {
+        let until: u64 = until.parse().expect("Invalid timestamp");
+
+        self.runtime.schedule_operation(&Operation::SelfExclude {
+            until: Timestamp::from(until),
+        });
+        vec![]
+    }
+
+    /// Set, raise or remove one of your responsible-gaming limits
+    ///
+    /// Schedules a SetGamingLimit operation to be executed by the contract.
+    async fn set_gaming_limit(&self, kind: String, period: String, amount: Option<String>) -> Vec<u8> {
+        let kind = match kind.to_uppercase().as_str() {
+            "DEPOSIT" => LimitKind::Deposit,
+            "LOSS" => LimitKind::Loss,
+            "WAGER" => LimitKind::Wager,
+            _ => panic!("Kind must be DEPOSIT, LOSS or WAGER"),
+        };
+        let period = match period.to_uppercase().as_str() {
+            "DAY" => LimitPeriod::Day,
+            "WEEK" => LimitPeriod::Week,
+            _ => panic!("Period must be DAY or WEEK"),
+        };
+        let amount = amount.map(|a| Amount::from_attos(a.parse().expect("Invalid amount")));
+
+        self.runtime.schedule_operation(&Operation::SetGamingLimit { kind, period, amount });
+        vec![]
+    }
+
+    /// Claim rakeback on fees paid
+    ///
+    /// Schedules a ClaimRakeback operation to be executed by the contract.
+    async fn claim_rakeback(&self) -> Vec<u8> {
+        self.runtime.schedule_operation(&Operation::ClaimRakeback);
+        vec![]
+    }
+
+    /// Register the player who referred you
+    ///
+    /// Schedules a RegisterReferrer operation to be executed by the contract.
+    async fn register_referrer(&self, referrer: String) -> Vec<u8> {
+        let referrer: AccountOwner = referrer.parse().expect("Invalid referrer address");
+
+        self.runtime.schedule_operation(&Operation::RegisterReferrer { referrer });
+        vec![]
+    }
+
+    /// Add liquidity to the house pool
+    ///
+    /// Schedules a ProvideLiquidity operation to be executed by the contract.
+    async fn provide_liquidity(&self, amount: String) -> Vec<u8> {
+        let amt: u128 = amount.parse().expect("Invalid amount");
+
+        self.runtime.schedule_operation(&Operation::ProvideLiquidity {
+            amount: Amount::from_attos(amt),
+        });
+        vec![]
+    }
+
+    /// Redeem LP shares from the house pool
+    ///
+    /// Schedules a WithdrawLiquidity operation to be executed by the contract.
+    async fn withdraw_liquidity(&self, shares: String) -> Vec<u8> {
+        let shares: u128 = shares.parse().expect("Invalid shares");
+
+        self.runtime.schedule_operation(&Operation::WithdrawLiquidity { shares });
+        vec![]
+    }
+
+    /// Let the house match players who have waited long enough
+    ///
+    /// Schedules a MatchWithHouse operation to be executed by the contract.
+    async fn match_with_house(&self) -> Vec<u8> {
+        self.runtime.schedule_operation(&Operation::MatchWithHouse);
+        vec![]
+    }
+
     /// Update price (oracle)
     ///
     /// Schedules an UpdatePrice operation to be executed by the contract.
-    async fn update_price(&self, asset: String, price: String) -> Vec<u8> {
-        let asset_enum = match asset.to_uppercase().as_str() {
-            "BTC" => Asset::BTC,
-            "ETH" => Asset::ETH,
-            _ => panic!("Unsupported asset"),
-        };
+    /// `price_decimal` is a decimal string in the asset's quote currency
+    /// (e.g. "65000.25" or "0.00001234"); it replaces the old integer
+    /// micro-USD `price` argument, which is no longer accepted.
+    async fn update_price(&self, asset: String, price_decimal: String) -> Vec<u8> {
+        // Registry membership is checked by the contract
+        let asset_enum = Asset::new(&asset);
 
-        let price_val: u64 = price.parse().expect("Invalid price");
+        let price_val: Price = price_decimal.parse().expect("Invalid price");
 
         self.runtime.schedule_operation(&Operation::UpdatePrice {
             asset: asset_enum,
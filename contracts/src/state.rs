@@ -1,8 +1,7 @@
 //! State definitions using Linera Views
 
 use crate::types::*;
-use async_graphql::SimpleObject;
-use linera_sdk::linera_base_types::{AccountOwner, Amount};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, Timestamp};
 use linera_sdk::views::{linera_views, MapView, QueueView, RegisterView, RootView, View, ViewStorageContext};
 
 /// Main application state using Linera Views
@@ -17,6 +16,9 @@ pub struct SpeedBetState {
     /// Platform fee in basis points (100 = 1%)
     pub fee_bps: RegisterView<u16>,
 
+    /// Discounted fee tiers by lifetime amount wagered, lowest threshold first
+    pub fee_tiers: RegisterView<Vec<FeeTier>>,
+
     /// Minimum bet amount
     pub min_bet: RegisterView<Amount>,
 
@@ -29,11 +31,29 @@ pub struct SpeedBetState {
     /// Is platform paused
     pub paused: RegisterView<bool>,
 
+    /// Duel durations players may choose from, in microseconds
+    pub duel_durations: RegisterView<Vec<u64>>,
+
+    /// Bucket edge and tiered payouts of new range duels
+    pub range_buckets: RegisterView<RangeBuckets>,
+
+    /// How new duels of each mode are settled (spot if unset)
+    pub settlement_modes: MapView<DuelMode, SettlementMode>,
+
     // ============ Matchmaking ============
 
     /// Matchmaking queue
     pub queue: QueueView<QueueEntry>,
 
+    /// Queue joins and leaves allowed per player per window
+    pub queue_rate_limit_ops: RegisterView<u32>,
+
+    /// Queue rate-limit window in microseconds
+    pub queue_rate_limit_window_micros: RegisterView<u64>,
+
+    /// Queue joins and leaves by player
+    pub queue_activity: MapView<AccountOwner, QueueActivity>,
+
     // ============ Duels ============
 
     /// Active duels by ID
@@ -45,6 +65,50 @@ pub struct SpeedBetState {
     /// Next duel ID counter
     pub next_duel_id: RegisterView<u64>,
 
+    /// Pending rematch offers by resolved duel ID
+    pub rematch_offers: MapView<u64, RematchOffer>,
+
+    // ============ Series ============
+
+    /// Best-of-N series by ID
+    pub series: MapView<u64, SeriesInfo>,
+
+    /// Next series ID counter
+    pub next_series_id: RegisterView<u64>,
+
+    // ============ Tournaments ============
+
+    /// Tournaments by ID
+    pub tournaments: MapView<u64, TournamentInfo>,
+
+    /// Next tournament ID counter
+    pub next_tournament_id: RegisterView<u64>,
+
+    // ============ Pool Rounds ============
+
+    /// Staking interval for pool rounds in microseconds (rounds run as long again once locked)
+    pub pool_interval_micros: RegisterView<u64>,
+
+    /// Open and locked pool rounds by ID
+    pub pool_rounds: MapView<u64, PoolRound>,
+
+    /// Round currently accepting stakes per asset
+    pub current_pool_rounds: MapView<Asset, u64>,
+
+    /// Settled pool rounds (recent history)
+    pub recent_pool_rounds: QueueView<PoolRound>,
+
+    /// Next pool round ID counter
+    pub next_round_id: RegisterView<u64>,
+
+    // ============ Side Pools ============
+
+    /// Spectator side pools of open and running duels by duel ID
+    pub side_pools: MapView<u64, SidePool>,
+
+    /// Settled side pools (recent history)
+    pub recent_side_pools: QueueView<SidePool>,
+
     // ============ Players ============
 
     /// Player statistics by owner
@@ -53,11 +117,108 @@ pub struct SpeedBetState {
     /// Player balances
     pub balances: MapView<AccountOwner, Amount>,
 
+    /// Responsible-gaming limits and activity by player
+    pub player_limits: MapView<AccountOwner, PlayerLimits>,
+
+    /// End of each player's self-exclusion
+    pub self_excluded_until: MapView<AccountOwner, Timestamp>,
+
+    /// Accounts blocked by the admin
+    pub blocked_accounts: MapView<AccountOwner, AccountBlock>,
+
+    // ============ Collusion Detection ============
+
+    /// Window for counting duels between the same pair in microseconds
+    pub pair_window_micros: RegisterView<u64>,
+
+    /// Duels between the same pair within a window that flag it
+    pub pair_flag_threshold: RegisterView<u32>,
+
+    /// Most duels matched between the same pair per window (unlimited if None)
+    pub pair_match_limit: RegisterView<Option<u32>>,
+
+    /// Share of settled duels with opposite predictions that flags a pair, once it
+    /// has settled at least the flag threshold of duels (off if None)
+    pub pair_opposite_ratio_bps: RegisterView<Option<u16>>,
+
+    /// Match history by pair of players, lower address first
+    pub pair_stats: MapView<(AccountOwner, AccountOwner), PairStats>,
+
+    // ============ Referrals ============
+
+    /// Share of a referred player's fees credited to their referrer in basis points
+    pub referral_fee_share_bps: RegisterView<u16>,
+
+    /// Referrer by referred player
+    pub referrers: MapView<AccountOwner, AccountOwner>,
+
+    /// Total referral earnings by referrer
+    pub referral_earnings: MapView<AccountOwner, Amount>,
+
+    // ============ Assets ============
+
+    /// Asset registry by symbol
+    pub assets: MapView<Asset, AssetConfig>,
+
     // ============ Prices ============
 
     /// Current prices by asset
     pub prices: MapView<Asset, PriceData>,
 
+    /// Recent quotes by asset, oldest first
+    pub price_history: MapView<Asset, Vec<PriceData>>,
+
+    /// Number of quotes kept per asset (older quotes still needed to settle are kept too)
+    pub price_history_len: RegisterView<u32>,
+
+    /// Assets halted by a circuit breaker
+    pub halted_assets: MapView<Asset, AssetHalt>,
+
+    // ============ House ============
+
+    /// Whether the house matches players left waiting in the queue
+    pub house_enabled: RegisterView<bool>,
+
+    /// How long a player waits before the house takes the other side
+    pub house_match_after_micros: RegisterView<u64>,
+
+    /// House stake locked in active duels by asset
+    pub house_exposure: MapView<Asset, Amount>,
+
+    /// LP shares by provider
+    pub lp_shares: MapView<AccountOwner, u128>,
+
+    /// Total LP shares outstanding
+    pub total_lp_shares: RegisterView<u128>,
+
+    // ============ Rakeback ============
+
+    /// Share of fees paid returned as rakeback in basis points
+    pub rakeback_bps: RegisterView<u16>,
+
+    /// Length of a rakeback epoch in microseconds
+    pub rakeback_epoch_micros: RegisterView<u64>,
+
+    /// Fees awaiting rakeback by player
+    pub rakeback_accounts: MapView<AccountOwner, RakebackAccount>,
+
+    /// Rakeback claims (recent history)
+    pub recent_rakeback_claims: QueueView<RakebackClaim>,
+
+    // ============ Jackpot ============
+
+    /// Share of each duel fee fed into the jackpot in basis points
+    pub jackpot_fee_share_bps: RegisterView<u16>,
+
+    /// Staked 1v1 duel win streak that wins the jackpot (every multiple of it)
+    pub jackpot_win_streak: RegisterView<u64>,
+
+    /// Current jackpot
+    pub jackpot_pool: RegisterView<Amount>,
+
+    /// Every jackpot paid out, oldest first
+    pub jackpot_wins: QueueView<JackpotWin>,
+
     // ============ Statistics ============
 
     /// Total platform volume
@@ -84,10 +245,50 @@ impl SpeedBetState {
         self.min_bet.set(min_bet);
         self.max_bet.set(max_bet);
         self.paused.set(false);
+        self.duel_durations.set(vec![DUEL_DURATION_MICROS]);
+        self.range_buckets.set(RangeBuckets::default());
         self.next_duel_id.set(1);
+        self.next_series_id.set(1);
+        self.next_tournament_id.set(1);
+        self.next_round_id.set(1);
+        self.pool_interval_micros.set(POOL_ROUND_INTERVAL_MICROS);
+        self.queue_rate_limit_ops.set(QUEUE_RATE_LIMIT_OPS);
+        self.queue_rate_limit_window_micros.set(QUEUE_RATE_LIMIT_WINDOW_MICROS);
+        self.pair_window_micros.set(PAIR_WINDOW_MICROS);
+        self.pair_flag_threshold.set(PAIR_FLAG_THRESHOLD);
+        self.pair_match_limit.set(None);
+        self.pair_opposite_ratio_bps.set(Some(PAIR_OPPOSITE_RATIO_BPS));
+        self.price_history_len.set(PRICE_HISTORY_LEN);
+        self.house_enabled.set(false);
+        self.house_match_after_micros.set(HOUSE_MATCH_AFTER_MICROS);
+        self.total_lp_shares.set(0);
+        self.referral_fee_share_bps.set(REFERRAL_FEE_SHARE_BPS);
+        self.rakeback_bps.set(RAKEBACK_BPS);
+        self.rakeback_epoch_micros.set(RAKEBACK_EPOCH_MICROS);
+        self.jackpot_fee_share_bps.set(0);
+        self.jackpot_win_streak.set(JACKPOT_WIN_STREAK);
+        self.jackpot_pool.set(Amount::ZERO);
         self.total_volume.set(Amount::ZERO);
         self.total_fees.set(Amount::ZERO);
         self.total_duels.set(0);
+
+        // List the launch markets
+        for symbol in ["BTC", "ETH"] {
+            let config = AssetConfig {
+                asset: Asset::new(symbol),
+                display_decimals: 2,
+                quote_currency: "USD".to_string(),
+                price_decimals: 6,
+                enabled: true,
+                min_bet,
+                max_bet,
+                oracle_sources: Vec::new(),
+                max_deviation_bps: None,
+                max_staleness_micros: None,
+                house_exposure_limit: Amount::ZERO,
+            };
+            self.set_asset_config(config).await;
+        }
     }
 
     /// Check if address is admin
@@ -98,6 +299,11 @@ impl SpeedBetState {
         }
     }
 
+    /// Check if a duel duration is on the configured list
+    pub fn is_allowed_duration(&self, duration_micros: u64) -> bool {
+        self.duel_durations.get().contains(&duration_micros)
+    }
+
     /// Get player balance
     pub async fn get_balance(&self, player: &AccountOwner) -> Amount {
         self.balances
@@ -126,6 +332,63 @@ impl SpeedBetState {
         Ok(())
     }
 
+    /// Get a player's responsible-gaming limits
+    pub async fn get_player_limits(&self, player: &AccountOwner) -> PlayerLimits {
+        self.player_limits.get(player).await.ok().flatten().unwrap_or_default()
+    }
+
+    /// Update a player's responsible-gaming limits
+    pub async fn update_player_limits(&mut self, player: &AccountOwner, limits: PlayerLimits) {
+        self.player_limits.insert(player, limits).unwrap();
+    }
+
+    /// Get the admin block on an account
+    pub async fn get_account_block(&self, account: &AccountOwner) -> Option<AccountBlock> {
+        self.blocked_accounts.get(account).await.ok().flatten()
+    }
+
+    /// Get when a player's self-exclusion ends
+    pub async fn get_self_exclusion(&self, player: &AccountOwner) -> Option<Timestamp> {
+        self.self_excluded_until.get(player).await.ok().flatten()
+    }
+
+    /// Get the referrer a player registered
+    pub async fn get_referrer(&self, player: &AccountOwner) -> Option<AccountOwner> {
+        self.referrers.get(player).await.ok().flatten()
+    }
+
+    /// Get total referral earnings of a referrer
+    pub async fn get_referral_earnings(&self, referrer: &AccountOwner) -> Amount {
+        self.referral_earnings.get(referrer).await.ok().flatten().unwrap_or_default()
+    }
+
+    /// Credit a referral payment to the referrer and track it
+    pub async fn credit_referral(&mut self, referrer: &AccountOwner, amount: Amount) {
+        self.credit(referrer, amount).await;
+        let earnings = self.get_referral_earnings(referrer).await.saturating_add(amount);
+        self.referral_earnings.insert(referrer, earnings).unwrap();
+    }
+
+    /// Get a player's fees awaiting rakeback
+    pub async fn get_rakeback_account(&self, player: &AccountOwner) -> RakebackAccount {
+        self.rakeback_accounts.get(player).await.ok().flatten().unwrap_or_default()
+    }
+
+    /// Update a player's fees awaiting rakeback
+    pub async fn update_rakeback_account(&mut self, player: &AccountOwner, account: RakebackAccount) {
+        self.rakeback_accounts.insert(player, account).unwrap();
+    }
+
+    /// Record a rakeback claim in history
+    pub async fn record_rakeback_claim(&mut self, claim: RakebackClaim) {
+        self.recent_rakeback_claims.push_back(claim);
+
+        // Keep only last 100 claims
+        while self.recent_rakeback_claims.count() > 100 {
+            self.recent_rakeback_claims.delete_front();
+        }
+    }
+
     /// Get player stats
     pub async fn get_stats(&self, player: &AccountOwner) -> PlayerStats {
         self.player_stats
@@ -153,40 +416,94 @@ impl SpeedBetState {
         self.queue.push_back(entry);
     }
 
-    /// Find and remove matching queue entry
-    pub async fn find_match(
-        &mut self,
-        player: &AccountOwner,
-        asset: Asset,
-        bet_amount: Amount,
-    ) -> Option<QueueEntry> {
+    /// Find and remove enough matching queue entries to fill a duel with `entry`.
+    ///
+    /// Returns the matched entries in queue order (`2 * team_size - 1` of them).
+    pub async fn find_match(&mut self, entry: &QueueEntry) -> Option<Vec<QueueEntry>> {
         let entries: Vec<_> = self.queue.elements().await.unwrap_or_default();
-
-        let mut match_index = None;
-        let mut matched_entry = None;
-
-        for (index, entry) in entries.iter().enumerate() {
-            if entry.asset == asset
-                && entry.bet_amount == bet_amount
-                && entry.player != *player
-            {
-                match_index = Some(index);
-                matched_entry = Some(entry.clone());
+        let needed = usize::from(entry.team_size) * 2 - 1;
+
+        // Queued players fill team one first, then team two alongside the
+        // joiner; skip anyone who has already met an opponent too often this
+        // window, and drop entries that can no longer cover their stake
+        let team_size = usize::from(entry.team_size);
+        let mut match_indices = Vec::new();
+        let mut evicted = Vec::new();
+        let mut team_one = Vec::new();
+        for (index, queued) in entries.iter().enumerate() {
+            if match_indices.len() == needed {
                 break;
             }
+            if !queued.matches(entry) {
+                continue;
+            }
+            if self.get_balance(&queued.player).await < queued.bet_amount {
+                evicted.push(index);
+                continue;
+            }
+            let on_team_one = match_indices.len() < team_size;
+            let opponents = if on_team_one {
+                vec![entry.player]
+            } else {
+                team_one.clone()
+            };
+            let mut at_limit = false;
+            for opponent in &opponents {
+                if self.pair_at_limit(&queued.player, opponent, entry.joined_at).await {
+                    at_limit = true;
+                    break;
+                }
+            }
+            if at_limit {
+                continue;
+            }
+            if on_team_one {
+                team_one.push(queued.player);
+            }
+            match_indices.push(index);
+        }
+        let found = match_indices.len() == needed;
+        if !found && evicted.is_empty() {
+            return None;
         }
 
-        if let Some(index) = match_index {
-            // Remove from queue (rebuild queue without this entry)
-            self.queue.clear();
-            for (i, e) in entries.into_iter().enumerate() {
-                if i != index {
-                    self.queue.push_back(e);
-                }
+        // Rebuild the queue without the evicted and matched entries
+        let mut matched = Vec::new();
+        self.queue.clear();
+        for (i, e) in entries.into_iter().enumerate() {
+            if evicted.contains(&i) {
+                continue;
+            }
+            if found && match_indices.contains(&i) {
+                matched.push(e);
+            } else {
+                self.queue.push_back(e);
             }
         }
 
-        matched_entry
+        if !found {
+            return None;
+        }
+        Some(matched)
+    }
+
+    /// Get match history of a pair of players
+    pub async fn get_pair_stats(&self, a: &AccountOwner, b: &AccountOwner) -> PairStats {
+        self.pair_stats.get(&pair_key(*a, *b)).await.ok().flatten().unwrap_or_default()
+    }
+
+    /// Update match history of a pair of players
+    pub async fn update_pair_stats(&mut self, a: &AccountOwner, b: &AccountOwner, stats: PairStats) {
+        self.pair_stats.insert(&pair_key(*a, *b), stats).unwrap();
+    }
+
+    /// Check if two players have met as often as the queue allows this window
+    pub async fn pair_at_limit(&self, a: &AccountOwner, b: &AccountOwner, now: Timestamp) -> bool {
+        let Some(limit) = *self.pair_match_limit.get() else {
+            return false;
+        };
+        let stats = self.get_pair_stats(a, b).await;
+        stats.duels_in_window(now, *self.pair_window_micros.get()) >= limit
     }
 
     /// Remove player from queue
@@ -206,6 +523,12 @@ impl SpeedBetState {
 
     /// Add active duel
     pub async fn add_duel(&mut self, duel: DuelInfo) {
+        if duel.is_house_duel() {
+            let exposure = self.get_house_exposure(&duel.asset).await;
+            self.house_exposure
+                .insert(&duel.asset, exposure.saturating_add(duel.house_stake()))
+                .unwrap();
+        }
         let id = duel.id.0;
         self.active_duels.insert(&id, duel).unwrap();
     }
@@ -226,6 +549,14 @@ impl SpeedBetState {
         // Remove from active
         self.active_duels.remove(&duel.id.0).unwrap();
 
+        // Release the house stake
+        if duel.is_house_duel() {
+            let exposure = self.get_house_exposure(&duel.asset).await;
+            self.house_exposure
+                .insert(&duel.asset, exposure.saturating_sub(duel.house_stake()))
+                .unwrap();
+        }
+
         // Add to recent history
         self.recent_duels.push_back(duel);
 
@@ -239,17 +570,291 @@ impl SpeedBetState {
         self.total_duels.set(total + 1);
     }
 
+    /// Find a completed duel in recent history
+    pub async fn get_recent_duel(&self, id: DuelId) -> Option<DuelInfo> {
+        let duels: Vec<_> = self.recent_duels.elements().await.unwrap_or_default();
+        duels.into_iter().find(|duel| duel.id == id)
+    }
+
+    /// Get pending rematch offer for a duel
+    pub async fn get_rematch_offer(&self, id: DuelId) -> Option<RematchOffer> {
+        self.rematch_offers.get(&id.0).await.ok().flatten()
+    }
+
+    /// Store rematch offer (replaces any previous offer for the duel)
+    pub async fn set_rematch_offer(&mut self, offer: RematchOffer) {
+        let id = offer.duel_id.0;
+        self.rematch_offers.insert(&id, offer).unwrap();
+    }
+
+    /// Remove rematch offer
+    pub async fn remove_rematch_offer(&mut self, id: DuelId) {
+        self.rematch_offers.remove(&id.0).unwrap();
+    }
+
+    /// Remove rematch offers that can no longer be accepted
+    pub async fn prune_rematch_offers(&mut self, now: Timestamp) {
+        let expired: Vec<_> = self.rematch_offers.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, offer)| offer.is_expired(now))
+            .map(|(id, _)| id)
+            .collect();
+        for id in expired {
+            self.rematch_offers.remove(&id).unwrap();
+        }
+    }
+
+    /// Get next series ID and increment
+    pub async fn next_series_id(&mut self) -> SeriesId {
+        let id = *self.next_series_id.get();
+        self.next_series_id.set(id + 1);
+        SeriesId(id)
+    }
+
+    /// Get series
+    pub async fn get_series(&self, id: SeriesId) -> Option<SeriesInfo> {
+        self.series.get(&id.0).await.ok().flatten()
+    }
+
+    /// Insert or update series
+    pub async fn update_series(&mut self, series: SeriesInfo) {
+        let id = series.id.0;
+        self.series.insert(&id, series).unwrap();
+    }
+
+    /// Get next tournament ID and increment
+    pub async fn next_tournament_id(&mut self) -> TournamentId {
+        let id = *self.next_tournament_id.get();
+        self.next_tournament_id.set(id + 1);
+        TournamentId(id)
+    }
+
+    /// Get tournament
+    pub async fn get_tournament(&self, id: TournamentId) -> Option<TournamentInfo> {
+        self.tournaments.get(&id.0).await.ok().flatten()
+    }
+
+    /// Insert or update tournament
+    pub async fn update_tournament(&mut self, tournament: TournamentInfo) {
+        let id = tournament.id.0;
+        self.tournaments.insert(&id, tournament).unwrap();
+    }
+
+    /// Get next pool round ID and increment
+    pub async fn next_round_id(&mut self) -> RoundId {
+        let id = *self.next_round_id.get();
+        self.next_round_id.set(id + 1);
+        RoundId(id)
+    }
+
+    /// Get open or locked pool round
+    pub async fn get_pool_round(&self, id: RoundId) -> Option<PoolRound> {
+        self.pool_rounds.get(&id.0).await.ok().flatten()
+    }
+
+    /// Insert or update pool round
+    pub async fn update_pool_round(&mut self, round: PoolRound) {
+        let id = round.id.0;
+        self.pool_rounds.insert(&id, round).unwrap();
+    }
+
+    /// Get the round currently accepting stakes for an asset
+    pub async fn current_pool_round(&self, asset: &Asset) -> Option<PoolRound> {
+        let id = self.current_pool_rounds.get(asset).await.ok().flatten()?;
+        self.get_pool_round(RoundId(id)).await
+    }
+
+    /// Make a round the one accepting stakes for its asset
+    pub async fn set_current_pool_round(&mut self, round: &PoolRound) {
+        self.current_pool_rounds.insert(&round.asset, round.id.0).unwrap();
+    }
+
+    /// Settle pool round and move to history
+    pub async fn complete_pool_round(&mut self, round: PoolRound) {
+        self.pool_rounds.remove(&round.id.0).unwrap();
+
+        if self.current_pool_rounds.get(&round.asset).await.ok().flatten() == Some(round.id.0) {
+            self.current_pool_rounds.remove(&round.asset).unwrap();
+        }
+
+        self.recent_pool_rounds.push_back(round);
+
+        // Keep only last 100 rounds
+        while self.recent_pool_rounds.count() > 100 {
+            self.recent_pool_rounds.delete_front();
+        }
+    }
+
+    /// Get side pool of a duel
+    pub async fn get_side_pool(&self, duel_id: DuelId) -> Option<SidePool> {
+        self.side_pools.get(&duel_id.0).await.ok().flatten()
+    }
+
+    /// Insert or update side pool
+    pub async fn update_side_pool(&mut self, pool: SidePool) {
+        let id = pool.duel_id.0;
+        self.side_pools.insert(&id, pool).unwrap();
+    }
+
+    /// Settle side pool and move to history
+    pub async fn complete_side_pool(&mut self, pool: SidePool) {
+        self.side_pools.remove(&pool.duel_id.0).unwrap();
+
+        self.recent_side_pools.push_back(pool);
+
+        // Keep only last 100 side pools
+        while self.recent_side_pools.count() > 100 {
+            self.recent_side_pools.delete_front();
+        }
+    }
+
+    /// Get how new duels of a mode are settled
+    pub async fn get_settlement_mode(&self, mode: DuelMode) -> SettlementMode {
+        self.settlement_modes.get(&mode).await.ok().flatten().unwrap_or_default()
+    }
+
+    /// Get registry entry for asset
+    pub async fn get_asset_config(&self, asset: &Asset) -> Option<AssetConfig> {
+        self.assets.get(asset).await.ok().flatten()
+    }
+
+    /// Insert or update registry entry for asset
+    pub async fn set_asset_config(&mut self, config: AssetConfig) {
+        let asset = config.asset.clone();
+        self.assets.insert(&asset, config).unwrap();
+    }
+
     /// Get current price for asset
     pub async fn get_price(&self, asset: &Asset) -> Option<PriceData> {
         self.prices.get(asset).await.ok().flatten()
     }
 
-    /// Set price for asset
+    /// Set price for asset and append it to the asset's history
     pub async fn set_price(&mut self, price: PriceData) {
-        let asset = price.asset;
+        let asset = price.asset.clone();
+
+        let retention = self.price_retention_micros().await;
+        let horizon = price.timestamp.micros().saturating_sub(retention);
+
+        let mut history = self.get_price_history(&asset).await;
+        history.push(price.clone());
+
+        // Past the length cap, only drop quotes that were superseded before the
+        // retention horizon, so duels and rounds can still look up their prices
+        let max_len = *self.price_history_len.get() as usize;
+        let excess = history.len().saturating_sub(max_len);
+        let superseded = history
+            .windows(2)
+            .take_while(|pair| pair[1].timestamp.micros() <= horizon)
+            .count();
+        history.drain(..excess.min(superseded));
+        self.price_history.insert(&asset, history).unwrap();
+
         self.prices.insert(&asset, price).unwrap();
     }
 
+    /// How far back quotes are needed: the longest duel plus the longest TWAP
+    /// window and the reveal window, or a pool round from opening to resolution
+    pub async fn price_retention_micros(&self) -> u64 {
+        let longest_duel = self.duel_durations.get().iter().copied().max().unwrap_or_default();
+        let twap_window = self.settlement_modes.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, settlement)| match settlement {
+                SettlementMode::Twap { window_micros } => window_micros,
+                SettlementMode::Spot => 0,
+            })
+            .max()
+            .unwrap_or_default();
+        let duel = longest_duel
+            .saturating_add(twap_window)
+            .saturating_add(REVEAL_WINDOW_MICROS);
+        let pool_round = self.pool_interval_micros.get().saturating_mul(2);
+        duel.max(pool_round)
+    }
+
+    /// Get recent quotes for asset, oldest first
+    pub async fn get_price_history(&self, asset: &Asset) -> Vec<PriceData> {
+        self.price_history
+            .get(asset)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    /// Get the quote in effect at a timestamp (latest quote not after it)
+    pub async fn price_at(&self, asset: &Asset, timestamp: Timestamp) -> Option<PriceData> {
+        self.get_price_history(asset)
+            .await
+            .into_iter()
+            .rev()
+            .find(|quote| quote.timestamp <= timestamp)
+    }
+
+    /// Time-weighted average price of asset over `[from, to]`
+    pub async fn twap(&self, asset: &Asset, from: Timestamp, to: Timestamp) -> Option<Price> {
+        time_weighted_average(&self.get_price_history(asset).await, from, to)
+    }
+
+    /// Get the circuit breaker record of a halted asset
+    pub async fn get_asset_halt(&self, asset: &Asset) -> Option<AssetHalt> {
+        self.halted_assets.get(asset).await.ok().flatten()
+    }
+
+    /// Halt asset and hold its running duels for review
+    pub async fn halt_asset(&mut self, halt: AssetHalt) -> Vec<DuelId> {
+        let mut held = Vec::new();
+        for (_, mut duel) in self.active_duels.index_values().await.unwrap_or_default() {
+            let affected = duel.asset == halt.asset || duel.versus_asset.as_ref() == Some(&halt.asset);
+            if affected && duel.status == DuelStatus::Active {
+                duel.status = DuelStatus::PendingReview;
+                held.push(duel.id);
+                self.update_duel(duel).await;
+            }
+        }
+        let asset = halt.asset.clone();
+        self.halted_assets.insert(&asset, halt).unwrap();
+        held
+    }
+
+    /// Lift the halt on asset
+    pub async fn clear_asset_halt(&mut self, asset: &Asset) {
+        self.halted_assets.remove(asset).unwrap();
+    }
+
+    /// Get house stake locked in active duels on asset
+    pub async fn get_house_exposure(&self, asset: &Asset) -> Amount {
+        self.house_exposure.get(asset).await.ok().flatten().unwrap_or_default()
+    }
+
+    /// Value of the house pool: free liquidity plus stakes locked in active duels
+    pub async fn house_pool_value(&self) -> Amount {
+        let locked = self.house_exposure.index_values().await
+            .unwrap_or_default()
+            .into_iter()
+            .fold(Amount::ZERO, |total, (_, amount)| total.saturating_add(amount));
+        self.get_balance(&HOUSE_ACCOUNT).await.saturating_add(locked)
+    }
+
+    /// Get LP shares held by provider
+    pub async fn get_lp_shares(&self, provider: &AccountOwner) -> u128 {
+        self.lp_shares.get(provider).await.ok().flatten().unwrap_or_default()
+    }
+
+    /// Set LP shares held by provider, updating the total
+    pub async fn set_lp_shares(&mut self, provider: &AccountOwner, shares: u128) {
+        let previous = self.get_lp_shares(provider).await;
+        let total = *self.total_lp_shares.get();
+        self.total_lp_shares.set(total - previous + shares);
+        if shares == 0 {
+            self.lp_shares.remove(provider).unwrap();
+        } else {
+            self.lp_shares.insert(provider, shares).unwrap();
+        }
+    }
+
     /// Record platform volume and fees
     pub async fn record_volume(&mut self, volume: Amount, fees: Amount) {
         let current_volume = *self.total_volume.get();
@@ -258,6 +863,11 @@ impl SpeedBetState {
         self.total_fees.set(current_fees.saturating_add(fees));
     }
 
+    /// Get a player's queue joins and leaves
+    pub async fn get_queue_activity(&self, player: &AccountOwner) -> QueueActivity {
+        self.queue_activity.get(player).await.ok().flatten().unwrap_or_default()
+    }
+
     /// Get queue length
     pub fn queue_length(&self) -> usize {
         self.queue.count()
@@ -1,6 +1,6 @@
 //! Core type definitions for SpeedBet Arena
 
-use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId, Timestamp};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, BcsHashable, CryptoHash, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// Unique identifier for a duel
@@ -19,32 +19,393 @@ impl From<u64> for DuelId {
     }
 }
 
-/// Supported assets for betting
+/// Unique identifier for a best-of-N series
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "UPPERCASE")]
-pub enum Asset {
-    /// Bitcoin
-    BTC,
-    /// Ethereum
-    ETH,
+pub struct SeriesId(pub u64);
+
+impl std::fmt::Display for SeriesId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for SeriesId {
+    fn from(value: u64) -> Self {
+        SeriesId(value)
+    }
+}
+
+/// Unique identifier for a tournament
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TournamentId(pub u64);
+
+impl std::fmt::Display for TournamentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for TournamentId {
+    fn from(value: u64) -> Self {
+        TournamentId(value)
+    }
+}
+
+/// Unique identifier for a pool round
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct RoundId(pub u64);
+
+impl std::fmt::Display for RoundId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for RoundId {
+    fn from(value: u64) -> Self {
+        RoundId(value)
+    }
 }
 
+/// Asset symbol (e.g. "BTC"), listed in the on-chain asset registry
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct Asset(String);
+
 impl Asset {
+    /// Create an asset from its symbol (normalized to uppercase)
+    pub fn new(symbol: &str) -> Self {
+        Asset(symbol.trim().to_uppercase())
+    }
+
     /// Returns the symbol string for the asset
-    pub fn symbol(&self) -> &'static str {
-        match self {
-            Asset::BTC => "BTC",
-            Asset::ETH => "ETH",
-        }
+    pub fn symbol(&self) -> &str {
+        &self.0
     }
 }
 
 impl Default for Asset {
     fn default() -> Self {
-        Asset::BTC
+        Asset::new("BTC")
+    }
+}
+
+impl std::fmt::Display for Asset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for Asset {
+    fn from(value: String) -> Self {
+        Asset::new(&value)
+    }
+}
+
+impl From<Asset> for String {
+    fn from(value: Asset) -> Self {
+        value.0
+    }
+}
+
+/// Fixed-point price: `value * 10^exponent` in the asset's quote currency
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Price {
+    /// Unscaled integer value
+    pub value: u128,
+    /// Power of ten applied to the value
+    pub exponent: i8,
+}
+
+impl Price {
+    /// Create a price from an unscaled value and exponent
+    pub fn new(value: u128, exponent: i8) -> Self {
+        Self { value, exponent }
+    }
+
+    /// Value expressed with the given exponent (saturating, truncating extra precision)
+    pub fn scaled_to(&self, exponent: i8) -> u128 {
+        let shift = u32::from(self.exponent.abs_diff(exponent));
+        if self.exponent >= exponent {
+            self.value.saturating_mul(10u128.saturating_pow(shift))
+        } else {
+            10u128.checked_pow(shift).map_or(0, |factor| self.value / factor)
+        }
+    }
+
+    /// Rescale to a fixed number of decimals
+    pub fn with_decimals(&self, decimals: u8) -> Price {
+        let exponent = -(decimals as i8);
+        Price::new(self.scaled_to(exponent), exponent)
+    }
+
+    /// Compare two prices after normalizing them to a common exponent
+    pub fn cmp_normalized(&self, other: &Price) -> std::cmp::Ordering {
+        let exponent = self.exponent.min(other.exponent);
+        self.scaled_to(exponent).cmp(&other.scaled_to(exponent))
+    }
+
+    /// Absolute difference from another price at a common exponent
+    pub fn distance(&self, other: &Price) -> u128 {
+        let exponent = self.exponent.min(other.exponent);
+        self.scaled_to(exponent).abs_diff(other.scaled_to(exponent))
+    }
+
+    /// Relative move from a reference price in basis points (saturating)
+    pub fn deviation_bps(&self, reference: &Price) -> u128 {
+        let exponent = self.exponent.min(reference.exponent);
+        let current = self.scaled_to(exponent);
+        let reference = reference.scaled_to(exponent);
+        if reference == 0 {
+            return if current == 0 { 0 } else { u128::MAX };
+        }
+        current.abs_diff(reference).saturating_mul(10_000) / reference
+    }
+}
+
+impl std::fmt::Display for Price {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.exponent >= 0 {
+            return write!(f, "{}", self.scaled_to(0));
+        }
+        let decimals = self.exponent.unsigned_abs() as usize;
+        let digits = format!("{:0>width$}", self.value, width = decimals + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - decimals);
+        write!(f, "{}.{}", int_part, frac_part)
+    }
+}
+
+impl std::str::FromStr for Price {
+    type Err = String;
+
+    /// Parse a decimal string such as "0.00001234" or "65000"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (int_part, frac_part) = s.trim().split_once('.').unwrap_or((s.trim(), ""));
+        let exponent = i8::try_from(frac_part.len())
+            .map_err(|_| format!("Too many decimals in price: {}", s))?;
+        let value = format!("{}{}", int_part, frac_part)
+            .parse::<u128>()
+            .map_err(|_| format!("Invalid price: {}", s))?;
+        Ok(Price::new(value, -exponent))
+    }
+}
+
+/// How start and end prices of a duel are determined
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SettlementMode {
+    /// Single quote at the start and end of the window
+    #[default]
+    Spot,
+    /// Time-weighted average of the quotes over a short window
+    Twap {
+        /// Averaging window in microseconds
+        window_micros: u64,
+    },
+}
+
+/// Registry entry describing a listed asset
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AssetConfig {
+    /// Asset symbol
+    pub asset: Asset,
+    /// Number of decimals to show in the UI
+    pub display_decimals: u8,
+    /// Currency prices are quoted in (e.g. "USD")
+    pub quote_currency: String,
+    /// Number of decimals prices are stored with
+    pub price_decimals: u8,
+    /// Whether new bets on the asset are accepted
+    pub enabled: bool,
+    /// Minimum bet amount
+    pub min_bet: Amount,
+    /// Maximum bet amount
+    pub max_bet: Amount,
+    /// Accounts allowed to report prices (anyone if empty)
+    pub oracle_sources: Vec<AccountOwner>,
+    /// Largest move from the previous quote before the market halts
+    pub max_deviation_bps: Option<u64>,
+    /// Longest gap between quotes before the market halts
+    pub max_staleness_micros: Option<u64>,
+    /// Most the house may have staked in active duels on the asset (house off if zero)
+    pub house_exposure_limit: Amount,
+}
+
+impl AssetConfig {
+    /// Check if a bet amount is within the asset's limits
+    pub fn accepts_bet(&self, amount: Amount) -> bool {
+        amount >= self.min_bet && amount <= self.max_bet
+    }
+
+    /// Check if an account may report prices for the asset
+    pub fn is_oracle_source(&self, reporter: Option<&AccountOwner>) -> bool {
+        self.oracle_sources.is_empty()
+            || reporter.is_some_and(|r| self.oracle_sources.contains(r))
+    }
+}
+
+/// Kind of prediction players make in a duel
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DuelMode {
+    /// Players call the price direction
+    #[default]
+    Direction,
+    /// Players pick a price-change bucket; the closest call wins
+    Range,
+    /// Players commit to a hidden target end price; the closest guess wins
+    ClosestPrice,
+    /// Players pick which of two assets has the better percentage return
+    Relative,
+}
+
+/// One of the two assets in a relative-performance duel
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Leg {
+    /// The duel's main asset
+    Base,
+    /// The asset it is compared against
+    Versus,
+}
+
+/// Price-change bucket for range predictions
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PriceBucket {
+    /// Fell by more than the bucket edge
+    DownLarge,
+    /// Fell by at most the bucket edge or stayed the same
+    DownSmall,
+    /// Rose by at most the bucket edge
+    UpSmall,
+    /// Rose by more than the bucket edge
+    UpLarge,
+}
+
+impl PriceBucket {
+    /// Bucket containing the move from start to end
+    pub fn of(start: &Price, end: &Price, edge_bps: u64) -> PriceBucket {
+        let large = end.deviation_bps(start) > u128::from(edge_bps);
+        match (end.cmp_normalized(start).is_gt(), large) {
+            (true, true) => PriceBucket::UpLarge,
+            (true, false) => PriceBucket::UpSmall,
+            (false, false) => PriceBucket::DownSmall,
+            (false, true) => PriceBucket::DownLarge,
+        }
+    }
+
+    /// Number of buckets between two buckets
+    pub fn distance(&self, other: &PriceBucket) -> u8 {
+        (*self as u8).abs_diff(*other as u8)
+    }
+}
+
+/// Bucket edge and tiered payouts of range duels
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RangeBuckets {
+    /// Move separating small from large buckets in basis points
+    pub edge_bps: u64,
+    /// Share of the losing stakes the winner takes in basis points, by how many
+    /// buckets the winning call missed (the last entry applies beyond)
+    pub payout_bps: Vec<u16>,
+}
+
+impl Default for RangeBuckets {
+    fn default() -> Self {
+        Self {
+            edge_bps: RANGE_BUCKET_BPS,
+            payout_bps: RANGE_PAYOUT_BPS.to_vec(),
+        }
+    }
+}
+
+impl RangeBuckets {
+    /// Share of the losing stakes won by a call `miss` buckets off
+    pub fn payout_for(&self, miss: u8) -> u16 {
+        self.payout_bps
+            .get(usize::from(miss))
+            .or(self.payout_bps.last())
+            .copied()
+            .unwrap_or(10000)
+    }
+}
+
+/// A player's call in a duel
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Prediction {
+    /// Price direction (direction duels)
+    Direction(Direction),
+    /// Price-change bucket (range duels)
+    Range(PriceBucket),
+    /// Committed target price (closest-price duels)
+    Guess(PriceGuess),
+    /// Asset expected to perform better (relative duels)
+    Outperformer(Leg),
+}
+
+impl Prediction {
+    /// Prediction taking the other side, if the mode has one
+    pub fn opposite(&self) -> Option<Prediction> {
+        match self {
+            Prediction::Direction(direction) => Some(Prediction::Direction(direction.opposite())),
+            Prediction::Outperformer(Leg::Base) => Some(Prediction::Outperformer(Leg::Versus)),
+            Prediction::Outperformer(Leg::Versus) => Some(Prediction::Outperformer(Leg::Base)),
+            Prediction::Range(_) | Prediction::Guess(_) => None,
+        }
+    }
+
+    /// Duel mode this prediction belongs to
+    pub fn mode(&self) -> DuelMode {
+        match self {
+            Prediction::Direction(_) => DuelMode::Direction,
+            Prediction::Range(_) => DuelMode::Range,
+            Prediction::Guess(_) => DuelMode::ClosestPrice,
+            Prediction::Outperformer(_) => DuelMode::Relative,
+        }
+    }
+}
+
+/// A hidden target price in a closest-price duel
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PriceGuess {
+    /// Hash of the `GuessReveal`
+    pub commitment: CryptoHash,
+    /// When the commitment was submitted (earlier wins ties)
+    pub committed_at: Timestamp,
+    /// Target end price, once revealed
+    pub target: Option<Price>,
+}
+
+/// Preimage of a closest-price guess commitment
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GuessReveal {
+    /// Duel the guess is for
+    pub duel_id: DuelId,
+    /// Target end price
+    pub target: Price,
+    /// Random salt keeping the target hidden
+    pub salt: String,
+}
+
+impl BcsHashable<'_> for GuessReveal {}
+
+impl GuessReveal {
+    /// Commitment to submit before the duel starts
+    pub fn commitment(&self) -> CryptoHash {
+        CryptoHash::new(self)
     }
 }
 
+/// Record of a tripped circuit breaker on an asset
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AssetHalt {
+    /// Halted asset
+    pub asset: Asset,
+    /// Why the circuit breaker tripped
+    pub reason: String,
+    /// Quote that tripped the breaker
+    pub price: Price,
+    /// When the asset was halted
+    pub halted_at: Timestamp,
+}
+
 /// Player's prediction direction
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Direction {
@@ -54,6 +415,16 @@ pub enum Direction {
     Down,
 }
 
+impl Direction {
+    /// Get the opposite direction
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+}
+
 /// Status of a duel
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
 pub enum DuelStatus {
@@ -64,12 +435,220 @@ pub enum DuelStatus {
     WaitingForPredictions,
     /// Predictions locked, timer running
     Active,
+    /// Held by a circuit breaker until an admin clears or voids it
+    PendingReview,
     /// Winner determined
     Resolved,
     /// Duel cancelled (timeout, etc.)
     Cancelled,
 }
 
+/// Status of a best-of-N series
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub enum SeriesStatus {
+    /// Challenge sent, waiting for opponent to accept
+    #[default]
+    Pending,
+    /// Stakes escrowed, duels being played
+    InProgress,
+    /// Series winner determined and paid out
+    Completed,
+    /// Series declined or cancelled, stakes refunded
+    Cancelled,
+}
+
+/// Status of a tournament
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub enum TournamentStatus {
+    /// Accepting registrations
+    #[default]
+    Registration,
+    /// Bracket seeded, rounds being played
+    InProgress,
+    /// Champion determined and prizes distributed
+    Completed,
+    /// Cancelled before start, entry fees refunded
+    Cancelled,
+}
+
+/// Status of a pool round
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub enum PoolRoundStatus {
+    /// Accepting stakes
+    #[default]
+    Open,
+    /// Start price locked, timer running
+    Locked,
+    /// Winning side paid out
+    Resolved,
+    /// One side had no stakes, everyone refunded
+    Refunded,
+}
+
+/// Status of a spectator side pool
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub enum SidePoolStatus {
+    /// Accepting bets until the duel starts
+    #[default]
+    Open,
+    /// Duel running, no more bets
+    Closed,
+    /// Backers of the winning team paid out
+    Settled,
+    /// Duel voided or one side had no bets, everyone refunded
+    Refunded,
+}
+
+/// Kind of responsible-gaming limit
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LimitKind {
+    /// Amount deposited
+    Deposit,
+    /// Stakes lost in settled duels
+    Loss,
+    /// Amount staked in duels
+    Wager,
+}
+
+/// Rolling period a responsible-gaming limit applies to
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LimitPeriod {
+    /// Last 24 hours
+    Day,
+    /// Last 7 days
+    Week,
+}
+
+impl LimitPeriod {
+    /// Length of the period in microseconds
+    pub fn micros(&self) -> u64 {
+        match self {
+            LimitPeriod::Day => 86_400_000_000,
+            LimitPeriod::Week => 7 * 86_400_000_000,
+        }
+    }
+}
+
+/// A limit change waiting out the cooling-off delay
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingLimit {
+    /// New limit (removed if None)
+    pub amount: Option<Amount>,
+    /// When the change takes effect
+    pub effective_at: Timestamp,
+}
+
+/// A player-configured responsible-gaming limit
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GamingLimit {
+    /// What the limit caps
+    pub kind: LimitKind,
+    /// Rolling period the limit applies to
+    pub period: LimitPeriod,
+    /// Limit in effect (unlimited if None)
+    pub amount: Option<Amount>,
+    /// Raise or removal waiting to take effect
+    pub pending: Option<PendingLimit>,
+}
+
+/// An amount counted towards a player's limits
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LimitUsage {
+    /// What the amount counts towards
+    pub kind: LimitKind,
+    /// Amount deposited, lost or staked
+    pub amount: Amount,
+    /// When it happened
+    pub at: Timestamp,
+}
+
+/// A player's responsible-gaming limits and recent activity
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlayerLimits {
+    /// Configured limits
+    pub limits: Vec<GamingLimit>,
+    /// Activity within the longest period
+    pub usage: Vec<LimitUsage>,
+}
+
+impl PlayerLimits {
+    /// Apply limit changes whose cooling-off delay has passed
+    pub fn apply_pending(&mut self, now: Timestamp) {
+        for limit in &mut self.limits {
+            if let Some(pending) = limit.pending.take() {
+                if now >= pending.effective_at {
+                    limit.amount = pending.amount;
+                } else {
+                    limit.pending = Some(pending);
+                }
+            }
+        }
+        self.limits.retain(|l| l.amount.is_some() || l.pending.is_some());
+    }
+
+    /// Set a limit; lowering takes effect now, raising or removing after the cooling-off delay
+    pub fn set_limit(
+        &mut self,
+        kind: LimitKind,
+        period: LimitPeriod,
+        amount: Option<Amount>,
+        now: Timestamp,
+    ) {
+        self.apply_pending(now);
+        let index = match self.limits.iter().position(|l| l.kind == kind && l.period == period) {
+            Some(index) => index,
+            None => {
+                self.limits.push(GamingLimit { kind, period, amount: None, pending: None });
+                self.limits.len() - 1
+            }
+        };
+        let limit = &mut self.limits[index];
+
+        let lowers = match (amount, limit.amount) {
+            (Some(new), Some(current)) => new <= current,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if lowers {
+            limit.amount = amount;
+            limit.pending = None;
+        } else {
+            limit.pending = Some(PendingLimit {
+                amount,
+                effective_at: Timestamp::from(now.micros().saturating_add(LIMIT_COOLING_OFF_MICROS)),
+            });
+        }
+        self.limits.retain(|l| l.amount.is_some() || l.pending.is_some());
+    }
+
+    /// Amount counted towards a kind of limit within a period
+    pub fn used(&self, kind: LimitKind, period: LimitPeriod, now: Timestamp) -> Amount {
+        let since = now.micros().saturating_sub(period.micros());
+        self.usage
+            .iter()
+            .filter(|u| u.kind == kind && u.at.micros() > since)
+            .fold(Amount::ZERO, |total, u| total.saturating_add(u.amount))
+    }
+
+    /// Check that `amount` more of a kind stays within every limit on it
+    pub fn allows(&self, kind: LimitKind, amount: Amount, now: Timestamp) -> bool {
+        self.limits
+            .iter()
+            .filter(|l| l.kind == kind)
+            .all(|l| match l.amount {
+                Some(cap) => self.used(kind, l.period, now).saturating_add(amount) <= cap,
+                None => true,
+            })
+    }
+
+    /// Count an amount towards a kind of limit, dropping activity older than a week
+    pub fn record(&mut self, kind: LimitKind, amount: Amount, now: Timestamp) {
+        let since = now.micros().saturating_sub(LimitPeriod::Week.micros());
+        self.usage.retain(|u| u.at.micros() > since);
+        self.usage.push(LimitUsage { kind, amount, at: now });
+    }
+}
+
 /// Player statistics
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PlayerStats {
@@ -85,6 +664,18 @@ pub struct PlayerStats {
     pub win_streak: u64,
     /// Best win streak ever
     pub best_streak: u64,
+    /// Total fees paid on settled duels
+    pub fees_paid: Amount,
+    /// Consecutive staked 1v1 duel wins (jackpot streak)
+    pub duel_win_streak: u64,
+    /// Total amount staked on side bets (not counted towards fee tiers)
+    pub side_bets_wagered: Amount,
+    /// Side bets won (kept apart from duel wins and streaks)
+    pub side_bet_wins: u64,
+    /// Side bets lost
+    pub side_bet_losses: u64,
+    /// Total paid out on winning side bets
+    pub side_bets_won: Amount,
 }
 
 impl PlayerStats {
@@ -109,11 +700,41 @@ impl PlayerStats {
         self.win_streak = 0;
     }
 
+    /// Record the outcome of a duel towards the jackpot streak
+    pub fn record_duel_result(&mut self, won_staked_1v1: bool) {
+        if won_staked_1v1 {
+            self.duel_win_streak += 1;
+        } else {
+            self.duel_win_streak = 0;
+        }
+    }
+
     /// Record a bet
     pub fn record_bet(&mut self, amount: Amount) {
         self.total_wagered = self.total_wagered.saturating_add(amount);
     }
 
+    /// Record a side bet
+    pub fn record_side_bet(&mut self, amount: Amount) {
+        self.side_bets_wagered = self.side_bets_wagered.saturating_add(amount);
+    }
+
+    /// Record a winning side bet
+    pub fn record_side_bet_win(&mut self, payout: Amount) {
+        self.side_bet_wins += 1;
+        self.side_bets_won = self.side_bets_won.saturating_add(payout);
+    }
+
+    /// Record a losing side bet
+    pub fn record_side_bet_loss(&mut self) {
+        self.side_bet_losses += 1;
+    }
+
+    /// Record a fee paid
+    pub fn record_fee(&mut self, amount: Amount) {
+        self.fees_paid = self.fees_paid.saturating_add(amount);
+    }
+
     /// Calculate win rate as percentage (0-100)
     pub fn win_rate(&self) -> u64 {
         let total = self.wins + self.losses;
@@ -124,6 +745,34 @@ impl PlayerStats {
     }
 }
 
+/// A player's queue joins and leaves, counted per rate-limit window
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct QueueActivity {
+    /// When the current window started
+    pub window_start: Timestamp,
+    /// Operations in the current window
+    pub window_count: u32,
+    /// Operations ever
+    pub total: u64,
+}
+
+impl QueueActivity {
+    /// Count an operation, starting a new window if the current one has passed;
+    /// returns false without counting if the window already holds `max_ops`
+    pub fn record(&mut self, now: Timestamp, window_micros: u64, max_ops: u32) -> bool {
+        if now.micros() >= self.window_start.micros().saturating_add(window_micros) {
+            self.window_start = now;
+            self.window_count = 0;
+        }
+        if self.window_count >= max_ops {
+            return false;
+        }
+        self.window_count += 1;
+        self.total += 1;
+        true
+    }
+}
+
 /// Queue entry for matchmaking
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueueEntry {
@@ -133,39 +782,122 @@ pub struct QueueEntry {
     pub asset: Asset,
     /// Bet amount in micro-units
     pub bet_amount: Amount,
+    /// Chosen duel duration in microseconds
+    pub duration_micros: u64,
+    /// Players per team (1 for a solo duel)
+    pub team_size: u8,
+    /// Kind of prediction the player wants to make
+    pub mode: DuelMode,
+    /// Asset to compare against (relative duels)
+    pub versus_asset: Option<Asset>,
     /// When player joined queue
     pub joined_at: Timestamp,
 }
 
+impl QueueEntry {
+    /// Check if another player's entry can be matched with this one
+    pub fn matches(&self, other: &QueueEntry) -> bool {
+        self.player != other.player
+            && self.asset == other.asset
+            && self.bet_amount == other.bet_amount
+            && self.duration_micros == other.duration_micros
+            && self.team_size == other.team_size
+            && self.mode == other.mode
+            && self.versus_asset == other.versus_asset
+    }
+}
+
+/// Side of a duel; single players form a team of one
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Team {
+    /// Team led by player1
+    One,
+    /// Team led by player2
+    Two,
+}
+
+impl Team {
+    /// Get the opposing team
+    pub fn opposite(&self) -> Team {
+        match self {
+            Team::One => Team::Two,
+            Team::Two => Team::One,
+        }
+    }
+}
+
+/// A player taking part in a duel
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Participant {
+    /// Player address
+    pub player: AccountOwner,
+    /// Team the player is on
+    pub team: Team,
+    /// Stake the player put into the pot
+    pub stake: Amount,
+    /// Fee charged on the stake at settlement
+    pub fee: Amount,
+}
+
 /// Information about a duel (stored in Lobby)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DuelInfo {
     /// Unique duel identifier
     pub id: DuelId,
-    /// First player (joined first)
+    /// First player (joined first), leading team one
     pub player1: AccountOwner,
-    /// Second player
+    /// Second player, leading team two
     pub player2: AccountOwner,
+    /// Everyone in the duel with their team and stake
+    pub participants: Vec<Participant>,
     /// Asset being bet on
     pub asset: Asset,
     /// Bet amount per player
     pub bet_amount: Amount,
+    /// Duel duration in microseconds
+    pub duration_micros: u64,
+    /// Kind of prediction players make
+    pub mode: DuelMode,
+    /// Asset compared against (relative duels)
+    pub versus_asset: Option<Asset>,
     /// Current status
     pub status: DuelStatus,
     /// When duel was created
     pub created_at: Timestamp,
     /// Winner (if resolved)
     pub winner: Option<AccountOwner>,
-    /// Player 1's prediction
-    pub p1_prediction: Option<Direction>,
-    /// Player 2's prediction
-    pub p2_prediction: Option<Direction>,
+    /// Team one's prediction
+    pub p1_prediction: Option<Prediction>,
+    /// Team two's prediction
+    pub p2_prediction: Option<Prediction>,
     /// Start price (when predictions locked)
-    pub start_price: Option<u64>,
+    pub start_price: Option<Price>,
     /// End price (when resolved)
-    pub end_price: Option<u64>,
+    pub end_price: Option<Price>,
     /// When duel started (predictions locked)
     pub started_at: Option<Timestamp>,
+    /// Duel this one is a rematch of (for series tracking)
+    pub previous_duel: Option<DuelId>,
+    /// Series this duel belongs to
+    pub series_id: Option<SeriesId>,
+    /// Tournament this duel belongs to
+    pub tournament_id: Option<TournamentId>,
+    /// How start and end prices are determined
+    pub settlement: SettlementMode,
+    /// Time-weighted start price (TWAP settlement)
+    pub start_twap: Option<Price>,
+    /// Time-weighted end price (TWAP settlement)
+    pub end_twap: Option<Price>,
+    /// Start price of the versus asset (relative duels)
+    pub versus_start_price: Option<Price>,
+    /// End price of the versus asset (relative duels)
+    pub versus_end_price: Option<Price>,
+    /// Time-weighted start price of the versus asset (relative duels, TWAP settlement)
+    pub versus_start_twap: Option<Price>,
+    /// Time-weighted end price of the versus asset (relative duels, TWAP settlement)
+    pub versus_end_twap: Option<Price>,
+    /// Bucket edge and payouts fixed at creation (range duels)
+    pub range_buckets: Option<RangeBuckets>,
 }
 
 impl DuelInfo {
@@ -176,14 +908,22 @@ impl DuelInfo {
         player2: AccountOwner,
         asset: Asset,
         bet_amount: Amount,
+        duration_micros: u64,
         created_at: Timestamp,
     ) -> Self {
         Self {
             id,
             player1,
             player2,
+            participants: vec![
+                Participant { player: player1, team: Team::One, stake: bet_amount, fee: Amount::ZERO },
+                Participant { player: player2, team: Team::Two, stake: bet_amount, fee: Amount::ZERO },
+            ],
             asset,
             bet_amount,
+            duration_micros,
+            mode: DuelMode::Direction,
+            versus_asset: None,
             status: DuelStatus::WaitingForPredictions,
             created_at,
             winner: None,
@@ -192,78 +932,959 @@ impl DuelInfo {
             start_price: None,
             end_price: None,
             started_at: None,
+            previous_duel: None,
+            series_id: None,
+            tournament_id: None,
+            settlement: SettlementMode::Spot,
+            start_twap: None,
+            end_twap: None,
+            versus_start_price: None,
+            versus_end_price: None,
+            versus_start_twap: None,
+            versus_end_twap: None,
+            range_buckets: None,
         }
     }
 
-    /// Check if both players have predicted
+    /// When the duel window ends (once started)
+    pub fn ends_at(&self) -> Option<Timestamp> {
+        let started_at = self.started_at?;
+        Some(Timestamp::from(started_at.micros().saturating_add(self.duration_micros)))
+    }
+
+    /// Replace the participants with two teams staking `bet_amount` each, led by their first players
+    pub fn set_teams(&mut self, team_one: &[AccountOwner], team_two: &[AccountOwner]) {
+        let stake = self.bet_amount;
+        let teams = [(team_one, Team::One), (team_two, Team::Two)];
+        self.participants = teams
+            .iter()
+            .flat_map(|(players, team)| {
+                players.iter().map(move |player| Participant {
+                    player: *player,
+                    team: *team,
+                    stake,
+                    fee: Amount::ZERO,
+                })
+            })
+            .collect();
+        self.player1 = team_one[0];
+        self.player2 = team_two[0];
+    }
+
+    /// Check if the house is the counterparty
+    pub fn is_house_duel(&self) -> bool {
+        self.team_of(&HOUSE_ACCOUNT).is_some()
+    }
+
+    /// Stake the house put into the duel
+    pub fn house_stake(&self) -> Amount {
+        self.participants
+            .iter()
+            .filter(|p| p.player == HOUSE_ACCOUNT)
+            .fold(Amount::ZERO, |total, p| total.saturating_add(p.stake))
+    }
+
+    /// Check if this is a team duel (more than one player per side)
+    pub fn is_team_duel(&self) -> bool {
+        self.participants.len() > 2
+    }
+
+    /// Check if both teams have predicted
     pub fn both_predicted(&self) -> bool {
         self.p1_prediction.is_some() && self.p2_prediction.is_some()
     }
 
     /// Check if a player is a participant
     pub fn is_participant(&self, player: &AccountOwner) -> bool {
-        self.player1 == *player || self.player2 == *player
+        self.team_of(player).is_some()
     }
 
-    /// Get the prediction for a player
-    pub fn get_prediction(&self, player: &AccountOwner) -> Option<Direction> {
-        if self.player1 == *player {
-            self.p1_prediction
-        } else if self.player2 == *player {
-            self.p2_prediction
-        } else {
-            None
-        }
+    /// Get the team a player is on
+    pub fn team_of(&self, player: &AccountOwner) -> Option<Team> {
+        self.participants.iter().find(|p| p.player == *player).map(|p| p.team)
     }
 
-    /// Set prediction for a player
-    pub fn set_prediction(&mut self, player: &AccountOwner, direction: Direction) -> bool {
-        if self.player1 == *player && self.p1_prediction.is_none() {
-            self.p1_prediction = Some(direction);
-            true
-        } else if self.player2 == *player && self.p2_prediction.is_none() {
-            self.p2_prediction = Some(direction);
-            true
-        } else {
-            false
-        }
+    /// Get the stakes of a team's members
+    pub fn team_stakes(&self, team: Team) -> Vec<(AccountOwner, Amount)> {
+        self.participants
+            .iter()
+            .filter(|p| p.team == team)
+            .map(|p| (p.player, p.stake))
+            .collect()
     }
 
-    /// Determine the winner based on price movement
-    pub fn determine_winner(&mut self) -> Option<AccountOwner> {
-        let start = self.start_price?;
-        let end = self.end_price?;
+    /// Every pair of players on opposing teams, leaving out the house
+    pub fn opposing_pairs(&self) -> Vec<(AccountOwner, AccountOwner)> {
+        let players = |team: Team| {
+            self.participants
+                .iter()
+                .filter(move |p| p.team == team && p.player != HOUSE_ACCOUNT)
+                .map(|p| p.player)
+        };
+        players(Team::One)
+            .flat_map(|a| players(Team::Two).map(move |b| pair_key(a, b)))
+            .collect()
+    }
+
+    /// Get the prediction slot of a player's team
+    fn prediction_slot(&mut self, player: &AccountOwner) -> Option<&mut Option<Prediction>> {
+        match self.team_of(player)? {
+            Team::One => Some(&mut self.p1_prediction),
+            Team::Two => Some(&mut self.p2_prediction),
+        }
+    }
+
+    /// Get the prediction of a player's team
+    pub fn get_prediction(&self, player: &AccountOwner) -> Option<Prediction> {
+        match self.team_of(player)? {
+            Team::One => self.p1_prediction,
+            Team::Two => self.p2_prediction,
+        }
+    }
+
+    /// Set the prediction for a player's team (any member may submit it, once)
+    pub fn set_prediction(&mut self, player: &AccountOwner, prediction: Prediction) -> bool {
+        match self.prediction_slot(player) {
+            Some(slot) if slot.is_none() => {
+                *slot = Some(prediction);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the closest-price guess of a player's team
+    pub fn guess_mut(&mut self, player: &AccountOwner) -> Option<&mut PriceGuess> {
+        let prediction = self.prediction_slot(player)?.as_mut();
+        match prediction {
+            Some(Prediction::Guess(guess)) => Some(guess),
+            _ => None,
+        }
+    }
+
+    /// Check if every closest-price guess has been revealed
+    pub fn guesses_revealed(&self) -> bool {
+        [self.p1_prediction, self.p2_prediction].iter().all(|prediction| match prediction {
+            Some(Prediction::Guess(guess)) => guess.target.is_some(),
+            _ => true,
+        })
+    }
+
+    /// Start and end prices used for settlement
+    pub fn settlement_prices(&self) -> Option<(Price, Price)> {
+        match self.settlement {
+            SettlementMode::Spot => Some((self.start_price?, self.end_price?)),
+            SettlementMode::Twap { .. } => Some((self.start_twap?, self.end_twap?)),
+        }
+    }
+
+    /// Start and end prices of the versus asset used for settlement (relative duels)
+    pub fn versus_settlement_prices(&self) -> Option<(Price, Price)> {
+        match self.settlement {
+            SettlementMode::Spot => Some((self.versus_start_price?, self.versus_end_price?)),
+            SettlementMode::Twap { .. } => Some((self.versus_start_twap?, self.versus_end_twap?)),
+        }
+    }
+
+    /// Determine the winner based on price movement.
+    ///
+    /// Returns `None` for a push: range predictions equally close to the actual move,
+    /// or no closest-price guess revealed.
+    pub fn determine_winner(&mut self) -> Option<AccountOwner> {
+        let (start, end) = self.settlement_prices()?;
         let p1_pred = self.p1_prediction?;
         let p2_pred = self.p2_prediction?;
 
-        let actual_direction = if end > start {
-            Direction::Up
-        } else {
-            Direction::Down
+        self.winner = match (p1_pred, p2_pred) {
+            (Prediction::Range(p1_bucket), Prediction::Range(p2_bucket)) => {
+                let edge_bps = self.range_buckets.as_ref().map_or(RANGE_BUCKET_BPS, |b| b.edge_bps);
+                let actual = PriceBucket::of(&start, &end, edge_bps);
+                match p1_bucket.distance(&actual).cmp(&p2_bucket.distance(&actual)) {
+                    std::cmp::Ordering::Less => Some(self.player1),
+                    std::cmp::Ordering::Greater => Some(self.player2),
+                    std::cmp::Ordering::Equal => None,
+                }
+            }
+            (Prediction::Guess(p1_guess), Prediction::Guess(p2_guess)) => {
+                // An unrevealed guess forfeits; equal distances go to the earlier commitment,
+                // then to player1
+                match (p1_guess.target, p2_guess.target) {
+                    (Some(p1_target), Some(p2_target)) => {
+                        let p1_key = (p1_target.distance(&end), p1_guess.committed_at);
+                        let p2_key = (p2_target.distance(&end), p2_guess.committed_at);
+                        if p2_key < p1_key {
+                            Some(self.player2)
+                        } else {
+                            Some(self.player1)
+                        }
+                    }
+                    (Some(_), None) => Some(self.player1),
+                    (None, Some(_)) => Some(self.player2),
+                    (None, None) => None,
+                }
+            }
+            _ => {
+                let actual_direction = if end.cmp_normalized(&start).is_gt() {
+                    Direction::Up
+                } else {
+                    Direction::Down
+                };
+
+                let p1_correct = p1_pred == Prediction::Direction(actual_direction);
+                let p2_correct = p2_pred == Prediction::Direction(actual_direction);
+
+                match (p1_correct, p2_correct) {
+                    (true, false) => Some(self.player1),
+                    (false, true) => Some(self.player2),
+                    // Both correct or both wrong - tie goes to player who predicted first
+                    // In this implementation, player1 always predicts first by design
+                    (true, true) => Some(self.player1),
+                    (false, false) => Some(self.player1),
+                }
+            }
         };
 
-        let p1_correct = p1_pred == actual_direction;
-        let p2_correct = p2_pred == actual_direction;
+        self.status = DuelStatus::Resolved;
+        self.winner
+    }
+
+    /// Determine the winner of a relative duel by comparing percentage returns.
+    ///
+    /// The base asset outperforms only with a strictly better return.
+    pub fn determine_relative_winner(&mut self) -> Option<AccountOwner> {
+        let (start, end) = self.settlement_prices()?;
+        let (versus_start, versus_end) = self.versus_settlement_prices()?;
+        let p1_pred = self.p1_prediction?;
+        let p2_pred = self.p2_prediction?;
+
+        // end / start > versus_end / versus_start, cross-multiplied in 256 bits
+        // once each leg's prices share an exponent
+        let base_exponent = start.exponent.min(end.exponent);
+        let versus_exponent = versus_start.exponent.min(versus_end.exponent);
+        let base_return = wide_mul(end.scaled_to(base_exponent), versus_start.scaled_to(versus_exponent));
+        let versus_return = wide_mul(versus_end.scaled_to(versus_exponent), start.scaled_to(base_exponent));
+        let outperformer = if base_return > versus_return { Leg::Base } else { Leg::Versus };
+
+        let p1_correct = p1_pred == Prediction::Outperformer(outperformer);
+        let p2_correct = p2_pred == Prediction::Outperformer(outperformer);
 
         self.winner = match (p1_correct, p2_correct) {
-            (true, false) => Some(self.player1),
             (false, true) => Some(self.player2),
-            // Both correct or both wrong - tie goes to player who predicted first
-            // In this implementation, player1 always predicts first by design
-            (true, true) => Some(self.player1),
-            (false, false) => Some(self.player1),
+            // As in direction duels, a tie goes to player1
+            _ => Some(self.player1),
         };
 
         self.status = DuelStatus::Resolved;
         self.winner
     }
 
-    /// Calculate total pot (both bets combined)
+    /// Share of the losing stakes the winner takes in basis points: tiered by
+    /// how close the winning call of a range duel was, the whole stake otherwise
+    pub fn winner_share_bps(&self) -> u16 {
+        let Some(buckets) = &self.range_buckets else {
+            return 10000;
+        };
+        let winning_call = match self.winner {
+            Some(winner) if winner == self.player1 => self.p1_prediction,
+            Some(_) => self.p2_prediction,
+            None => None,
+        };
+        match (winning_call, self.settlement_prices()) {
+            (Some(Prediction::Range(bucket)), Some((start, end))) => {
+                let actual = PriceBucket::of(&start, &end, buckets.edge_bps);
+                buckets.payout_for(bucket.distance(&actual))
+            }
+            _ => 10000,
+        }
+    }
+
+    /// Calculate total pot (all stakes combined)
     pub fn total_pot(&self) -> Amount {
-        self.bet_amount.saturating_add(self.bet_amount)
+        self.participants
+            .iter()
+            .fold(Amount::ZERO, |total, p| total.saturating_add(p.stake))
+    }
+
+    /// Total fee charged at settlement
+    pub fn total_fee(&self) -> Amount {
+        self.participants
+            .iter()
+            .fold(Amount::ZERO, |total, p| total.saturating_add(p.fee))
+    }
+
+    /// Get the other participant of the duel
+    pub fn opponent_of(&self, player: &AccountOwner) -> Option<AccountOwner> {
+        if self.player1 == *player {
+            Some(self.player2)
+        } else if self.player2 == *player {
+            Some(self.player1)
+        } else {
+            None
+        }
+    }
+}
+
+/// A pending rematch offer for a resolved duel
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RematchOffer {
+    /// The resolved duel being rematched
+    pub duel_id: DuelId,
+    /// Player who offered the rematch
+    pub offered_by: AccountOwner,
+    /// Player who may accept the rematch
+    pub opponent: AccountOwner,
+    /// Asset to bet on
+    pub asset: Asset,
+    /// Bet amount per player for the rematch
+    pub bet_amount: Amount,
+    /// Duel duration in microseconds
+    pub duration_micros: u64,
+    /// Kind of prediction players make
+    pub mode: DuelMode,
+    /// Asset compared against (relative duels)
+    pub versus_asset: Option<Asset>,
+    /// When the offer was made
+    pub offered_at: Timestamp,
+}
+
+impl RematchOffer {
+    /// Check if the offer can no longer be accepted
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        now.micros() > self.offered_at.micros().saturating_add(REMATCH_WINDOW_MICROS)
+    }
+}
+
+/// A best-of-N series of duels between two players
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeriesInfo {
+    /// Unique series identifier
+    pub id: SeriesId,
+    /// Player who issued the challenge
+    pub player1: AccountOwner,
+    /// Challenged player
+    pub player2: AccountOwner,
+    /// Asset being bet on
+    pub asset: Asset,
+    /// Series stake per player
+    pub stake: Amount,
+    /// Maximum number of duels in the series (odd)
+    pub best_of: u32,
+    /// Duration of each duel in microseconds
+    pub duration_micros: u64,
+    /// Duels played so far, in order
+    pub duel_ids: Vec<DuelId>,
+    /// Duels won by player 1
+    pub p1_wins: u32,
+    /// Duels won by player 2
+    pub p2_wins: u32,
+    /// Current status
+    pub status: SeriesStatus,
+    /// Series winner (if completed)
+    pub winner: Option<AccountOwner>,
+    /// When the challenge was issued
+    pub created_at: Timestamp,
+}
+
+impl SeriesInfo {
+    /// Create a new pending series
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: SeriesId,
+        player1: AccountOwner,
+        player2: AccountOwner,
+        asset: Asset,
+        stake: Amount,
+        best_of: u32,
+        duration_micros: u64,
+        created_at: Timestamp,
+    ) -> Self {
+        Self {
+            id,
+            player1,
+            player2,
+            asset,
+            stake,
+            best_of,
+            duration_micros,
+            duel_ids: Vec::new(),
+            p1_wins: 0,
+            p2_wins: 0,
+            status: SeriesStatus::Pending,
+            winner: None,
+            created_at,
+        }
+    }
+
+    /// Number of duel wins required to take the series
+    pub fn wins_needed(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    /// Check if a player is a participant
+    pub fn is_participant(&self, player: &AccountOwner) -> bool {
+        self.player1 == *player || self.player2 == *player
+    }
+
+    /// Record the winner of a series duel, returning the series winner once decided
+    pub fn record_duel_winner(&mut self, winner: &AccountOwner) -> Option<AccountOwner> {
+        if self.player1 == *winner {
+            self.p1_wins += 1;
+        } else if self.player2 == *winner {
+            self.p2_wins += 1;
+        }
+
+        if self.p1_wins >= self.wins_needed() {
+            self.winner = Some(self.player1);
+        } else if self.p2_wins >= self.wins_needed() {
+            self.winner = Some(self.player2);
+        }
+        self.winner
+    }
+
+    /// Calculate series pot (both stakes combined)
+    pub fn total_pot(&self) -> Amount {
+        self.stake.saturating_add(self.stake)
+    }
+}
+
+/// A single match in a tournament bracket
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BracketMatch {
+    /// Round number (0 = first round)
+    pub round: u32,
+    /// Position within the round
+    pub slot: u32,
+    /// First player
+    pub player1: AccountOwner,
+    /// Second player
+    pub player2: AccountOwner,
+    /// Duel currently deciding this match (None if decided by forfeit)
+    pub duel_id: Option<DuelId>,
+    /// Winner (once the duel is resolved)
+    pub winner: Option<AccountOwner>,
+}
+
+impl BracketMatch {
+    /// Get the player eliminated in this match
+    pub fn loser(&self) -> Option<AccountOwner> {
+        let winner = self.winner?;
+        if winner == self.player1 {
+            Some(self.player2)
+        } else {
+            Some(self.player1)
+        }
+    }
+}
+
+/// A single-elimination tournament
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TournamentInfo {
+    /// Unique tournament identifier
+    pub id: TournamentId,
+    /// Asset being bet on
+    pub asset: Asset,
+    /// Entry fee per player
+    pub entry_fee: Amount,
+    /// Duration of each duel in microseconds
+    pub duration_micros: u64,
+    /// Number of players (power of two)
+    pub size: u32,
+    /// Prize pool share per placement tier in basis points (champion first)
+    pub prize_split: Vec<u16>,
+    /// Registered players, in seeding order
+    pub players: Vec<AccountOwner>,
+    /// Bracket matches across all rounds
+    pub bracket: Vec<BracketMatch>,
+    /// Round currently being played
+    pub current_round: u32,
+    /// Current status
+    pub status: TournamentStatus,
+    /// Collected entry fees
+    pub prize_pool: Amount,
+    /// Tournament champion (if completed)
+    pub champion: Option<AccountOwner>,
+    /// When the tournament was created
+    pub created_at: Timestamp,
+}
+
+impl TournamentInfo {
+    /// Create a new tournament open for registration
+    pub fn new(
+        id: TournamentId,
+        asset: Asset,
+        entry_fee: Amount,
+        duration_micros: u64,
+        size: u32,
+        prize_split: Vec<u16>,
+        created_at: Timestamp,
+    ) -> Self {
+        Self {
+            id,
+            asset,
+            entry_fee,
+            duration_micros,
+            size,
+            prize_split,
+            players: Vec::new(),
+            bracket: Vec::new(),
+            current_round: 0,
+            status: TournamentStatus::Registration,
+            prize_pool: Amount::ZERO,
+            champion: None,
+            created_at,
+        }
+    }
+
+    /// Total number of rounds in the bracket
+    pub fn rounds(&self) -> u32 {
+        self.size.trailing_zeros()
+    }
+
+    /// Number of placement tiers (champion, runner-up, semifinalists, ...)
+    pub fn placement_tiers(&self) -> usize {
+        self.rounds() as usize + 1
+    }
+
+    /// Check if all seats are taken
+    pub fn is_full(&self) -> bool {
+        self.players.len() as u32 >= self.size
+    }
+
+    /// Check if a player is registered
+    pub fn is_registered(&self, player: &AccountOwner) -> bool {
+        self.players.contains(player)
+    }
+
+    /// First-round pairings: seed 1 plays seed N, seed 2 plays seed N-1, ...
+    pub fn first_round_pairings(&self) -> Vec<(AccountOwner, AccountOwner)> {
+        let n = self.players.len();
+        (0..n / 2)
+            .map(|i| (self.players[i], self.players[n - 1 - i]))
+            .collect()
+    }
+
+    /// Matches of a given round
+    pub fn round_matches(&self, round: u32) -> impl Iterator<Item = &BracketMatch> {
+        self.bracket.iter().filter(move |m| m.round == round)
+    }
+
+    /// Find the bracket match decided by a duel
+    pub fn match_for_duel_mut(&mut self, duel_id: DuelId) -> Option<&mut BracketMatch> {
+        self.bracket.iter_mut().find(|m| m.duel_id == Some(duel_id))
+    }
+
+    /// Check if every match of the current round has a winner
+    pub fn current_round_complete(&self) -> bool {
+        self.round_matches(self.current_round).all(|m| m.winner.is_some())
+    }
+
+    /// Next-round pairings: winners of adjacent slots play each other
+    pub fn next_round_pairings(&self) -> Vec<(AccountOwner, AccountOwner)> {
+        let mut matches: Vec<_> = self.round_matches(self.current_round).collect();
+        matches.sort_by_key(|m| m.slot);
+        matches
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [a, b] => Some((a.winner?, b.winner?)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Players grouped by placement tier, champion first
+    pub fn placements(&self) -> Vec<Vec<AccountOwner>> {
+        let mut tiers = Vec::new();
+        if let Some(champion) = self.champion {
+            tiers.push(vec![champion]);
+        }
+        for round in (0..self.rounds()).rev() {
+            tiers.push(self.round_matches(round).filter_map(|m| m.loser()).collect());
+        }
+        tiers
+    }
+}
+
+/// A player's stake in a pool round
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PoolEntry {
+    /// Player address
+    pub player: AccountOwner,
+    /// Predicted direction
+    pub direction: Direction,
+    /// Staked amount
+    pub amount: Amount,
+}
+
+/// A multi-player parimutuel UP/DOWN round
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PoolRound {
+    /// Unique round identifier
+    pub id: RoundId,
+    /// Asset being bet on
+    pub asset: Asset,
+    /// When staking opened
+    pub opens_at: Timestamp,
+    /// When staking closes and the start price is locked
+    pub locks_at: Timestamp,
+    /// When the round can be resolved
+    pub resolves_at: Timestamp,
+    /// Stakes placed in the round
+    pub entries: Vec<PoolEntry>,
+    /// Start price (when locked)
+    pub start_price: Option<Price>,
+    /// End price (when resolved)
+    pub end_price: Option<Price>,
+    /// Winning direction (when resolved)
+    pub outcome: Option<Direction>,
+    /// Current status
+    pub status: PoolRoundStatus,
+}
+
+impl PoolRound {
+    /// Create a new round whose staking window is `[opens_at, opens_at + interval)`
+    /// and which runs for one more interval after locking
+    pub fn new(id: RoundId, asset: Asset, opens_at: Timestamp, interval_micros: u64) -> Self {
+        let locks_at = Timestamp::from(opens_at.micros().saturating_add(interval_micros));
+        let resolves_at = Timestamp::from(locks_at.micros().saturating_add(interval_micros));
+        Self {
+            id,
+            asset,
+            opens_at,
+            locks_at,
+            resolves_at,
+            entries: Vec::new(),
+            start_price: None,
+            end_price: None,
+            outcome: None,
+            status: PoolRoundStatus::Open,
+        }
+    }
+
+    /// Check if the round still accepts stakes
+    pub fn accepts_stakes(&self, now: Timestamp) -> bool {
+        self.status == PoolRoundStatus::Open && now < self.locks_at
+    }
+
+    /// Check if a player has already staked
+    pub fn has_entry(&self, player: &AccountOwner) -> bool {
+        self.entries.iter().any(|e| e.player == *player)
+    }
+
+    /// Total staked on a direction
+    pub fn total_for(&self, direction: Direction) -> Amount {
+        self.entries
+            .iter()
+            .filter(|e| e.direction == direction)
+            .fold(Amount::ZERO, |total, e| total.saturating_add(e.amount))
+    }
+
+    /// Total staked on both sides
+    pub fn total_staked(&self) -> Amount {
+        self.total_for(Direction::Up).saturating_add(self.total_for(Direction::Down))
     }
 }
 
+/// A spectator's bet on one side of a duel
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SideBet {
+    /// Spectator address
+    pub bettor: AccountOwner,
+    /// Team backed
+    pub team: Team,
+    /// Staked amount
+    pub amount: Amount,
+}
+
+/// Parimutuel pool of spectator bets on a duel
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SidePool {
+    /// Duel being bet on
+    pub duel_id: DuelId,
+    /// Bets placed
+    pub bets: Vec<SideBet>,
+    /// Winning team (when settled)
+    pub winning_team: Option<Team>,
+    /// Current status
+    pub status: SidePoolStatus,
+}
+
+impl SidePool {
+    /// Create an empty pool for a duel
+    pub fn new(duel_id: DuelId) -> Self {
+        Self {
+            duel_id,
+            bets: Vec::new(),
+            winning_team: None,
+            status: SidePoolStatus::Open,
+        }
+    }
+
+    /// Get the team a spectator has backed
+    pub fn team_of(&self, bettor: &AccountOwner) -> Option<Team> {
+        self.bets.iter().find(|b| b.bettor == *bettor).map(|b| b.team)
+    }
+
+    /// Total staked on a team
+    pub fn total_for(&self, team: Team) -> Amount {
+        self.bets
+            .iter()
+            .filter(|b| b.team == team)
+            .fold(Amount::ZERO, |total, b| total.saturating_add(b.amount))
+    }
+
+    /// Total staked on both teams
+    pub fn total_staked(&self) -> Amount {
+        self.total_for(Team::One).saturating_add(self.total_for(Team::Two))
+    }
+}
+
+/// A player's fees awaiting rakeback
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RakebackAccount {
+    /// Fees paid since the last claim
+    pub unclaimed_fees: Amount,
+    /// Rakeback set aside from those fees at settlement
+    pub accrued: Amount,
+    /// When the player last claimed
+    pub last_claimed_at: Option<Timestamp>,
+    /// Epoch (system time divided by the epoch length) of the last claim
+    pub last_claimed_epoch: Option<u64>,
+}
+
+/// A rakeback payout
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RakebackClaim {
+    /// Claiming player
+    pub player: AccountOwner,
+    /// Fees the rakeback was paid on
+    pub fees: Amount,
+    /// Amount paid out
+    pub amount: Amount,
+    /// When the claim was made
+    pub claimed_at: Timestamp,
+}
+
+/// An admin sanction keeping an account from betting
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccountBlock {
+    /// Why the account was blocked
+    pub reason: String,
+    /// Admin who blocked it
+    pub blocked_by: AccountOwner,
+    /// When it was blocked
+    pub blocked_at: Timestamp,
+}
+
+/// Order two players so a pair has a single key
+pub fn pair_key(a: AccountOwner, b: AccountOwner) -> (AccountOwner, AccountOwner) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// How often two players have been matched against each other
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PairStats {
+    /// Duels played against each other
+    pub duels: u64,
+    /// Duels between them that settled
+    pub settled: u64,
+    /// Settled duels where they made opposite predictions
+    pub opposite_predictions: u64,
+    /// When the current window started
+    pub window_start: Timestamp,
+    /// Duels in the current window
+    pub window_duels: u32,
+    /// When the pair was flagged as suspicious
+    pub flagged_at: Option<Timestamp>,
+}
+
+impl PairStats {
+    /// Duels in the window running at `now`
+    pub fn duels_in_window(&self, now: Timestamp, window_micros: u64) -> u32 {
+        if now.micros() >= self.window_start.micros().saturating_add(window_micros) {
+            0
+        } else {
+            self.window_duels
+        }
+    }
+
+    /// Count a duel, starting a new window if the current one has passed
+    pub fn record_duel(&mut self, now: Timestamp, window_micros: u64) {
+        self.window_duels = self.duels_in_window(now, window_micros);
+        if self.window_duels == 0 {
+            self.window_start = now;
+        }
+        self.window_duels += 1;
+        self.duels += 1;
+    }
+
+    /// Share of settled duels with opposite predictions in basis points
+    pub fn opposite_ratio_bps(&self) -> u64 {
+        if self.settled == 0 {
+            return 0;
+        }
+        self.opposite_predictions.saturating_mul(10_000) / self.settled
+    }
+}
+
+/// A jackpot payout, kept as an audit trail
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JackpotWin {
+    /// Winning player
+    pub player: AccountOwner,
+    /// Duel that triggered the jackpot
+    pub duel_id: DuelId,
+    /// Win streak reached in that duel
+    pub win_streak: u64,
+    /// Amount paid out
+    pub amount: Amount,
+    /// When the jackpot was won
+    pub won_at: Timestamp,
+}
+
+/// Fee rate applied to players who have wagered at least `min_wagered`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// Lifetime amount wagered to qualify
+    pub min_wagered: Amount,
+    /// Fee in basis points
+    pub fee_bps: u16,
+}
+
+/// Fee rate for a player: the highest tier they qualify for, or `base_fee_bps`
+pub fn tier_fee_bps(tiers: &[FeeTier], total_wagered: Amount, base_fee_bps: u16) -> u16 {
+    tiers
+        .iter()
+        .rev()
+        .find(|tier| total_wagered >= tier.min_wagered)
+        .map_or(base_fee_bps, |tier| tier.fee_bps)
+}
+
+/// Calculate the platform fee on an amount: `amount * fee_bps / 10000`
+pub fn platform_fee(amount: Amount, fee_bps: u16) -> Amount {
+    Amount::from_attos(u128::from(amount) * u128::from(fee_bps) / 10000)
+}
+
+/// Full 256-bit product of two u128 values as (hi, lo), ordered like the product
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let mid = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let lo = (lo_lo & MASK) | (mid << 64);
+    let hi = a_hi * b_hi + (hi_lo >> 64) + (lo_hi >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// Compute `a * b / c` without overflowing the intermediate product.
+///
+/// The quotient must fit in a `u128`, which holds whenever `b <= c`.
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product / c;
+    }
+
+    let (hi, lo) = wide_mul(a, b);
+
+    // Long division of the product by c
+    let mut quotient: u128 = 0;
+    let mut remainder: u128 = 0;
+    for i in (0..256).rev() {
+        let bit = if i >= 128 { (hi >> (i - 128)) & 1 } else { (lo >> i) & 1 };
+        let carry = remainder >> 127 == 1;
+        remainder = (remainder << 1) | bit;
+        quotient <<= 1;
+        if carry || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+    quotient
+}
+
+/// Split an amount between stakes in proportion to their size.
+///
+/// Returns the shares and the rounding dust left over.
+pub fn pro_rata_split(
+    stakes: &[(AccountOwner, Amount)],
+    amount: Amount,
+) -> (Vec<(AccountOwner, Amount)>, Amount) {
+    let stake_total = stakes
+        .iter()
+        .fold(Amount::ZERO, |total, (_, stake)| total.saturating_add(*stake));
+
+    let mut paid = Amount::ZERO;
+    let shares: Vec<_> = stakes
+        .iter()
+        .map(|(player, stake)| {
+            let share = if stake_total == Amount::ZERO {
+                Amount::ZERO
+            } else {
+                Amount::from_attos(mul_div(u128::from(amount), u128::from(*stake), u128::from(stake_total)))
+            };
+            paid = paid.saturating_add(share);
+            (*player, share)
+        })
+        .collect();
+
+    (shares, amount.saturating_sub(paid))
+}
+
+/// Shares minted for liquidity added to a house pool worth `pool_value`
+pub fn lp_shares_for(amount: Amount, pool_value: Amount, total_shares: u128) -> u128 {
+    if total_shares == 0 || pool_value == Amount::ZERO {
+        return u128::from(amount);
+    }
+    mul_div(u128::from(amount), total_shares, u128::from(pool_value))
+}
+
+/// Current value of LP shares in a house pool worth `pool_value`
+pub fn lp_share_value(shares: u128, pool_value: Amount, total_shares: u128) -> Amount {
+    if total_shares == 0 {
+        return Amount::ZERO;
+    }
+    Amount::from_attos(mul_div(u128::from(pool_value), shares, total_shares))
+}
+
+/// Split a parimutuel pool between the winning stakes.
+///
+/// Each winner gets their stake back plus a pro-rata share of the losing side's stakes
+/// after the platform fee. Returns the payouts and the amount kept by the platform
+/// (fee plus rounding dust).
+pub fn parimutuel_payouts(
+    winning: &[(AccountOwner, Amount)],
+    losing_total: Amount,
+    fee_bps: u16,
+) -> (Vec<(AccountOwner, Amount)>, Amount) {
+    let winning_total = winning
+        .iter()
+        .fold(Amount::ZERO, |total, (_, amount)| total.saturating_add(*amount));
+    let distributable = losing_total.saturating_sub(platform_fee(losing_total, fee_bps));
+
+    let mut paid = Amount::ZERO;
+    let payouts: Vec<_> = winning
+        .iter()
+        .map(|(player, stake)| {
+            let share = if winning_total == Amount::ZERO {
+                0
+            } else {
+                mul_div(u128::from(distributable), u128::from(*stake), u128::from(winning_total))
+            };
+            let payout = stake.saturating_add(Amount::from_attos(share));
+            paid = paid.saturating_add(Amount::from_attos(share));
+            (*player, payout)
+        })
+        .collect();
+
+    (payouts, losing_total.saturating_sub(paid))
+}
+
 /// Leaderboard entry
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LeaderboardEntry {
@@ -280,15 +1901,99 @@ pub struct LeaderboardEntry {
 pub struct PriceData {
     /// Asset type
     pub asset: Asset,
-    /// Price in micro-USD (1 USD = 1_000_000)
-    pub price: u64,
+    /// Price in the asset's quote currency
+    pub price: Price,
     /// When price was fetched
     pub timestamp: Timestamp,
 }
 
-/// Duration of a duel in microseconds (60 seconds)
+/// Default number of quotes kept in each asset's price history
+pub const PRICE_HISTORY_LEN: u32 = 120;
+
+/// Maximum number of quotes kept in each asset's price history
+pub const MAX_PRICE_HISTORY_LEN: u32 = 1_000;
+
+/// Maximum number of decimals an asset's prices may be stored with
+pub const MAX_PRICE_DECIMALS: u8 = 18;
+
+/// Compute the time-weighted average price over `[from, to]`.
+///
+/// `quotes` must be ordered oldest first. The quote in effect at `from` (the latest one not
+/// after it) covers the start of the window; if there is none, averaging starts at the first
+/// quote inside the window. Returns `None` if no quote is in effect during the window.
+pub fn time_weighted_average(quotes: &[PriceData], from: Timestamp, to: Timestamp) -> Option<Price> {
+    let first = quotes.iter().rposition(|q| q.timestamp <= from).unwrap_or(0);
+    let window: Vec<_> = quotes[first..].iter().filter(|q| q.timestamp <= to).collect();
+    let last = window.last()?;
+    let exponent = window.iter().map(|q| q.price.exponent).min()?;
+
+    let mut weighted_sum: u128 = 0;
+    let mut total_weight: u128 = 0;
+    for (i, quote) in window.iter().enumerate() {
+        let start = quote.timestamp.max(from).micros();
+        let end = window.get(i + 1).map_or(to, |next| next.timestamp).micros();
+        let weight = u128::from(end.saturating_sub(start));
+        weighted_sum = weighted_sum.saturating_add(quote.price.scaled_to(exponent).saturating_mul(weight));
+        total_weight += weight;
+    }
+
+    if total_weight == 0 {
+        return Some(last.price);
+    }
+    Some(Price::new(weighted_sum / total_weight, exponent))
+}
+
+/// Default boundary between small and large price-change buckets in basis points (0.5%)
+pub const RANGE_BUCKET_BPS: u64 = 50;
+
+/// Default share of the losing stakes a range duel winner takes, by buckets missed
+pub const RANGE_PAYOUT_BPS: [u16; 3] = [10_000, 5_000, 2_500];
+
+/// How long after a duel ends players may still reveal their guesses (30 seconds)
+pub const REVEAL_WINDOW_MICROS: u64 = 30_000_000;
+
+/// Account holding the house liquidity pool and playing house duels
+pub const HOUSE_ACCOUNT: AccountOwner = AccountOwner::Reserved(1);
+
+/// Default wait before the house takes a queued player's bet (30 seconds)
+pub const HOUSE_MATCH_AFTER_MICROS: u64 = 30_000_000;
+
+/// Default window for counting duels between the same pair in microseconds (1 hour)
+pub const PAIR_WINDOW_MICROS: u64 = 3_600_000_000;
+
+/// Default duels between the same pair within a window that flag it
+pub const PAIR_FLAG_THRESHOLD: u32 = 10;
+
+/// Default share of a pair's settled duels with opposite predictions that flags it (95%)
+pub const PAIR_OPPOSITE_RATIO_BPS: u16 = 9_500;
+
+/// Default win streak that wins the jackpot
+pub const JACKPOT_WIN_STREAK: u64 = 10;
+
+/// Maximum players per team in a team duel
+pub const MAX_TEAM_SIZE: u8 = 5;
+
+/// Default duration of a duel in microseconds (60 seconds)
 pub const DUEL_DURATION_MICROS: u64 = 60_000_000;
 
+/// Rematch offer window in microseconds (30 seconds)
+pub const REMATCH_WINDOW_MICROS: u64 = 30_000_000;
+
+/// Maximum number of duels in a series
+pub const MAX_SERIES_LENGTH: u32 = 9;
+
+/// Maximum number of players in a tournament
+pub const MAX_TOURNAMENT_SIZE: u32 = 64;
+
+/// Default pool round staking interval in microseconds (60 seconds)
+pub const POOL_ROUND_INTERVAL_MICROS: u64 = 60_000_000;
+
+/// Default queue joins and leaves allowed per player per window
+pub const QUEUE_RATE_LIMIT_OPS: u32 = 10;
+
+/// Default queue rate-limit window in microseconds (1 minute)
+pub const QUEUE_RATE_LIMIT_WINDOW_MICROS: u64 = 60_000_000;
+
 /// Queue timeout in microseconds (5 minutes)
 pub const QUEUE_TIMEOUT_MICROS: u64 = 300_000_000;
 
@@ -300,3 +2005,329 @@ pub const MAX_BET: u64 = 100_000_000; // 100 tokens
 
 /// Platform fee in basis points (200 = 2%)
 pub const PLATFORM_FEE_BPS: u16 = 200;
+
+/// Default share of a referred player's fees credited to their referrer in basis points (10%)
+pub const REFERRAL_FEE_SHARE_BPS: u16 = 1_000;
+
+/// Default share of fees paid returned as rakeback in basis points (10%)
+pub const RAKEBACK_BPS: u16 = 1_000;
+
+/// Default rakeback epoch in microseconds (1 day)
+pub const RAKEBACK_EPOCH_MICROS: u64 = 86_400_000_000;
+
+/// Delay before a raised or removed gaming limit takes effect in microseconds (24 hours)
+pub const LIMIT_COOLING_OFF_MICROS: u64 = 86_400_000_000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(n: u8) -> AccountOwner {
+        AccountOwner::Reserved(n)
+    }
+
+    /// Price with 18 decimals
+    fn price_18(units: u128) -> Price {
+        Price::new(units * 10u128.pow(18), -18)
+    }
+
+    fn attos(value: u128) -> Amount {
+        Amount::from_attos(value)
+    }
+
+    fn quote(price: u128, at: u64) -> PriceData {
+        PriceData {
+            asset: Asset::new("BTC"),
+            price: Price::new(price, 0),
+            timestamp: Timestamp::from(at),
+        }
+    }
+
+    fn relative_duel(start: Price, end: Price, versus_start: Price, versus_end: Price) -> DuelInfo {
+        let mut duel = DuelInfo::new(
+            DuelId(1),
+            player(10),
+            player(11),
+            Asset::new("ETH"),
+            Amount::ONE,
+            60_000_000,
+            Timestamp::from(0),
+        );
+        duel.mode = DuelMode::Relative;
+        duel.versus_asset = Some(Asset::new("BTC"));
+        duel.p1_prediction = Some(Prediction::Outperformer(Leg::Base));
+        duel.p2_prediction = Some(Prediction::Outperformer(Leg::Versus));
+        duel.start_price = Some(start);
+        duel.end_price = Some(end);
+        duel.versus_start_price = Some(versus_start);
+        duel.versus_end_price = Some(versus_end);
+        duel
+    }
+
+    fn range_duel(p1_bucket: PriceBucket, p2_bucket: PriceBucket, end: u128) -> DuelInfo {
+        let mut duel = DuelInfo::new(
+            DuelId(1),
+            player(10),
+            player(11),
+            Asset::new("BTC"),
+            Amount::ONE,
+            60_000_000,
+            Timestamp::from(0),
+        );
+        duel.mode = DuelMode::Range;
+        duel.range_buckets = Some(RangeBuckets { edge_bps: 100, payout_bps: vec![10_000, 5_000] });
+        duel.p1_prediction = Some(Prediction::Range(p1_bucket));
+        duel.p2_prediction = Some(Prediction::Range(p2_bucket));
+        duel.start_price = Some(Price::new(10_000, 0));
+        duel.end_price = Some(Price::new(end, 0));
+        duel
+    }
+
+    #[test]
+    fn relative_winner_with_18_decimal_prices() {
+        // ETH +10% against BTC +5%: the cross products overflow u128
+        let mut duel = relative_duel(price_18(3_000), price_18(3_300), price_18(60_000), price_18(63_000));
+        assert_eq!(duel.determine_relative_winner(), Some(player(10)));
+
+        // ETH +5% against BTC +10%
+        let mut duel = relative_duel(price_18(3_000), price_18(3_150), price_18(60_000), price_18(66_000));
+        assert_eq!(duel.determine_relative_winner(), Some(player(11)));
+    }
+
+    #[test]
+    fn relative_winner_normalizes_exponents() {
+        // Same ETH +10% quoted once at 2 and once at 18 decimals, BTC +5%
+        let start = Price::new(300_000, -2);
+        let mut duel = relative_duel(start, price_18(3_300), Price::new(60_000, 0), Price::new(63_000, 0));
+        assert_eq!(duel.determine_relative_winner(), Some(player(10)));
+    }
+
+    #[test]
+    fn bracket_pairs_seeds_and_winners() {
+        let mut tournament = TournamentInfo::new(
+            TournamentId(1),
+            Asset::new("BTC"),
+            attos(10),
+            60_000_000,
+            4,
+            vec![7_000, 3_000],
+            Timestamp::from(0),
+        );
+        tournament.players = (10..14).map(player).collect();
+        let pairings = tournament.first_round_pairings();
+        assert_eq!(pairings, vec![(player(10), player(13)), (player(11), player(12))]);
+
+        for (slot, (player1, player2)) in pairings.into_iter().enumerate() {
+            tournament.bracket.push(BracketMatch {
+                round: 0,
+                slot: slot as u32,
+                player1,
+                player2,
+                duel_id: Some(DuelId(slot as u64)),
+                winner: None,
+            });
+        }
+        assert!(!tournament.current_round_complete());
+
+        tournament.match_for_duel_mut(DuelId(0)).unwrap().winner = Some(player(13));
+        tournament.match_for_duel_mut(DuelId(1)).unwrap().winner = Some(player(11));
+        assert!(tournament.current_round_complete());
+        assert_eq!(tournament.next_round_pairings(), vec![(player(13), player(11))]);
+
+        tournament.champion = Some(player(11));
+        tournament.bracket.push(BracketMatch {
+            round: 1,
+            slot: 0,
+            player1: player(13),
+            player2: player(11),
+            duel_id: Some(DuelId(2)),
+            winner: Some(player(11)),
+        });
+        assert_eq!(
+            tournament.placements(),
+            vec![vec![player(11)], vec![player(13)], vec![player(10), player(12)]]
+        );
+    }
+
+    #[test]
+    fn mul_div_uses_wide_product() {
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), u128::MAX);
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 120), 1 << 80);
+        assert_eq!(mul_div(u128::MAX, 3, 4), u128::MAX / 4 * 3 + 2);
+        assert_eq!(mul_div(10, 20, 7), 28);
+    }
+
+    #[test]
+    fn pro_rata_split_conserves_pot() {
+        let stakes = [(player(10), attos(1)), (player(11), attos(1)), (player(12), attos(1))];
+        let (shares, dust) = pro_rata_split(&stakes, attos(100));
+        assert!(shares.iter().all(|(_, share)| *share == attos(33)));
+        assert_eq!(dust, attos(1));
+
+        let stakes = [(player(10), attos(u128::MAX / 3)), (player(11), attos(u128::MAX / 5))];
+        let pot = attos(u128::MAX - 7);
+        let (shares, dust) = pro_rata_split(&stakes, pot);
+        let paid = shares.iter().fold(dust, |total, (_, share)| total.saturating_add(*share));
+        assert_eq!(paid, pot);
+        assert!(shares[0].1 > shares[1].1);
+
+        let (shares, dust) = pro_rata_split(&[(player(10), Amount::ZERO)], attos(5));
+        assert_eq!(shares, vec![(player(10), Amount::ZERO)]);
+        assert_eq!(dust, attos(5));
+    }
+
+    #[test]
+    fn parimutuel_payouts_conserve_pool() {
+        let winning = [(player(10), attos(300)), (player(11), attos(700))];
+        let (payouts, kept) = parimutuel_payouts(&winning, attos(1_001), 200);
+
+        // 2% of 1001 is 20, leaving 981 to split 3:7
+        assert_eq!(payouts, vec![(player(10), attos(300 + 294)), (player(11), attos(700 + 686))]);
+        assert_eq!(kept, attos(21));
+        let paid = payouts.iter().fold(kept, |total, (_, payout)| total.saturating_add(*payout));
+        assert_eq!(paid, attos(1_000 + 1_001));
+    }
+
+    #[test]
+    fn price_parsing_and_normalization() {
+        assert_eq!("0.00001234".parse::<Price>(), Ok(Price::new(1234, -8)));
+        assert_eq!("65000".parse::<Price>(), Ok(Price::new(65000, 0)));
+        assert!("6.5.0".parse::<Price>().is_err());
+        assert!("abc".parse::<Price>().is_err());
+        assert_eq!(Price::new(1234, -8).to_string(), "0.00001234");
+        assert_eq!(Price::new(65000, 0).to_string(), "65000");
+
+        let price = Price::new(6_543_210, -2);
+        assert_eq!(price.with_decimals(6), Price::new(65_432_100_000, -6));
+        assert_eq!(price.with_decimals(0), Price::new(65_432, 0));
+
+        // 18-decimal prices compare by value whatever their exponent
+        assert!(price_18(65_432).cmp_normalized(&price).is_lt());
+        assert!(price_18(65_433).cmp_normalized(&price).is_gt());
+        assert_eq!(price_18(3_000).deviation_bps(&Price::new(2_000, 0)), 5_000);
+    }
+
+    #[test]
+    fn twap_weights_quotes_across_gaps() {
+        let quotes = [quote(100, 0), quote(200, 10), quote(400, 100)];
+
+        // 100 for 10 micros, then 200 for the 90-micro gap
+        assert_eq!(
+            time_weighted_average(&quotes, Timestamp::from(0), Timestamp::from(100)),
+            Some(Price::new(190, 0))
+        );
+        // The quote in effect at the window start covers it
+        assert_eq!(
+            time_weighted_average(&quotes, Timestamp::from(50), Timestamp::from(150)),
+            Some(Price::new(300, 0))
+        );
+        // A window before any quote has no average
+        let later = [quote(100, 50)];
+        assert_eq!(time_weighted_average(&later, Timestamp::from(0), Timestamp::from(40)), None);
+        // Averaging starts at the first quote inside the window
+        assert_eq!(
+            time_weighted_average(&later, Timestamp::from(0), Timestamp::from(60)),
+            Some(Price::new(100, 0))
+        );
+    }
+
+    #[test]
+    fn price_moves_fall_into_buckets_around_the_edge() {
+        let start = Price::new(10_000, 0);
+        assert_eq!(PriceBucket::of(&start, &Price::new(10_100, 0), 100), PriceBucket::UpSmall);
+        assert_eq!(PriceBucket::of(&start, &Price::new(10_101, 0), 100), PriceBucket::UpLarge);
+        assert_eq!(PriceBucket::of(&start, &start, 100), PriceBucket::DownSmall);
+        assert_eq!(PriceBucket::of(&start, &Price::new(9_899, 0), 100), PriceBucket::DownLarge);
+    }
+
+    #[test]
+    fn closest_range_call_wins_a_tiered_share() {
+        // Exact call takes every losing stake
+        let mut duel = range_duel(PriceBucket::UpLarge, PriceBucket::DownSmall, 10_200);
+        assert_eq!(duel.determine_winner(), Some(player(10)));
+        assert_eq!(duel.winner_share_bps(), 10_000);
+
+        // A call one bucket off takes half
+        let mut duel = range_duel(PriceBucket::DownLarge, PriceBucket::UpSmall, 10_200);
+        assert_eq!(duel.determine_winner(), Some(player(11)));
+        assert_eq!(duel.winner_share_bps(), 5_000);
+
+        // Calls equally far off push
+        let mut duel = range_duel(PriceBucket::UpLarge, PriceBucket::DownSmall, 10_050);
+        assert_eq!(duel.determine_winner(), None);
+    }
+
+    #[test]
+    fn lp_shares_round_trip() {
+        // The first deposit mints shares one to one
+        assert_eq!(lp_shares_for(attos(1_000), Amount::ZERO, 0), 1_000);
+
+        // A pool that doubled in value mints half as many shares
+        let shares = lp_shares_for(attos(500), attos(2_000), 1_000);
+        assert_eq!(shares, 250);
+        assert_eq!(lp_share_value(shares, attos(2_500), 1_250), attos(500));
+        assert_eq!(lp_share_value(10, attos(100), 0), Amount::ZERO);
+
+        // Large pools go through the wide path without overflowing
+        let pool = attos(u128::MAX / 2);
+        let total_shares = u128::MAX / 4;
+        assert_eq!(lp_share_value(total_shares, pool, total_shares), pool);
+    }
+
+    #[test]
+    fn fee_tier_is_the_highest_one_reached() {
+        let tiers = [
+            FeeTier { min_wagered: attos(100), fee_bps: 150 },
+            FeeTier { min_wagered: attos(1_000), fee_bps: 100 },
+        ];
+        assert_eq!(tier_fee_bps(&tiers, attos(99), 200), 200);
+        assert_eq!(tier_fee_bps(&tiers, attos(100), 200), 150);
+        assert_eq!(tier_fee_bps(&tiers, attos(5_000), 200), 100);
+        assert_eq!(tier_fee_bps(&[], attos(5_000), 200), 200);
+    }
+
+    #[test]
+    fn lowering_a_limit_applies_now_and_raising_waits() {
+        let now = Timestamp::from(1_000);
+        let mut limits = PlayerLimits::default();
+        limits.set_limit(LimitKind::Wager, LimitPeriod::Day, Some(attos(100)), now);
+        limits.record(LimitKind::Wager, attos(80), now);
+        assert!(limits.allows(LimitKind::Wager, attos(20), now));
+        assert!(!limits.allows(LimitKind::Wager, attos(21), now));
+
+        // Lowering takes effect immediately
+        limits.set_limit(LimitKind::Wager, LimitPeriod::Day, Some(attos(90)), now);
+        assert!(!limits.allows(LimitKind::Wager, attos(11), now));
+
+        // Raising waits out the cooling-off delay
+        limits.set_limit(LimitKind::Wager, LimitPeriod::Day, Some(attos(500)), now);
+        let before = Timestamp::from(1_000 + LIMIT_COOLING_OFF_MICROS - 1);
+        limits.apply_pending(before);
+        assert!(!limits.allows(LimitKind::Wager, attos(11), before));
+        let after = Timestamp::from(1_000 + LIMIT_COOLING_OFF_MICROS);
+        limits.apply_pending(after);
+        assert!(limits.allows(LimitKind::Wager, attos(400), after));
+
+        // Removing a limit waits too
+        limits.set_limit(LimitKind::Wager, LimitPeriod::Day, None, after);
+        assert!(!limits.allows(LimitKind::Wager, attos(501), after));
+        let removed = Timestamp::from(after.micros() + LIMIT_COOLING_OFF_MICROS);
+        limits.apply_pending(removed);
+        assert!(limits.limits.is_empty());
+    }
+
+    #[test]
+    fn queue_activity_allows_max_ops_per_window() {
+        let mut activity = QueueActivity::default();
+        let at = Timestamp::from;
+        assert!(activity.record(at(1_000), 100, 2));
+        assert!(activity.record(at(1_050), 100, 2));
+        assert!(!activity.record(at(1_099), 100, 2));
+
+        // A rejected operation is not counted, and a new window starts afresh
+        assert!(activity.record(at(1_100), 100, 2));
+        assert_eq!(activity.window_start, at(1_100));
+        assert_eq!(activity.window_count, 1);
+        assert_eq!(activity.total, 3);
+    }
+}
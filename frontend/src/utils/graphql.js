@@ -246,10 +246,11 @@ export const WITHDRAW = `
 
 /**
  * Update price (oracle only)
+ * priceDecimal is a decimal string in the quote currency, e.g. "65000.25"
  */
 export const UPDATE_PRICE = `
-  mutation UpdatePrice($asset: String!, $price: String!) {
-    updatePrice(asset: $asset, price: $price)
+  mutation UpdatePrice($asset: String!, $priceDecimal: String!) {
+    updatePrice(asset: $asset, priceDecimal: $priceDecimal)
   }
 `;
 